@@ -0,0 +1,415 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Serialize;
+use tokio::{net::TcpListener, sync::Mutex, task::JoinSet};
+
+use crate::{
+    domain::{
+        error::DomainError,
+        health::repository::HealthRepository,
+        sync_run::{model::SyncRunModel, repository::SyncRunRepository, resources::find},
+    },
+    infrastructure::metrics,
+};
+
+use super::{lib::SyncJob, shutdown::Shutdown, supervisor::Supervisor};
+
+/// What the admin server reports on and can trigger.
+pub struct AdminState {
+    databases: Vec<(&'static str, Arc<dyn HealthRepository>)>,
+    sync_run_repository: Arc<dyn SyncRunRepository>,
+    jobs: Vec<Arc<dyn SyncJob>>,
+    supervisor: Supervisor,
+    runs: Mutex<JoinSet<()>>,
+}
+
+impl AdminState {
+    pub fn new(
+        databases: Vec<(&'static str, Arc<dyn HealthRepository>)>,
+        sync_run_repository: Arc<dyn SyncRunRepository>,
+        jobs: Vec<Arc<dyn SyncJob>>,
+        supervisor: Supervisor,
+    ) -> Self {
+        Self {
+            databases,
+            sync_run_repository,
+            jobs,
+            supervisor,
+            runs: Mutex::new(JoinSet::new()),
+        }
+    }
+}
+
+pub fn router(state: Arc<AdminState>) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/metrics", get(scrape))
+        .route("/jobs", get(jobs))
+        .route("/jobs/{name}/run", post(run_job))
+        .with_state(state)
+}
+
+/// Serves the admin endpoints on `addr` until shutdown, then waits for the
+/// runs it triggered to finish like scheduled ones.
+pub async fn serve(addr: &str, state: AdminState, shutdown: Shutdown) -> Result<(), DomainError> {
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|err| DomainError::InternalServerError(format!("Failed to bind admin server to {}: {}", addr, err)))?;
+    tracing::info!("Admin server listening on {}", addr);
+
+    let state = Arc::new(state);
+
+    axum::serve(listener, router(state.clone()))
+        .with_graceful_shutdown(async move { shutdown.requested().await })
+        .await
+        .map_err(|err| DomainError::InternalServerError(format!("Admin server failed: {}", err)))?;
+
+    let mut runs = state.runs.lock().await;
+    while runs.join_next().await.is_some() {}
+
+    Ok(())
+}
+
+async fn healthz() -> &'static str {
+    "ok"
+}
+
+async fn scrape() -> Result<String, DomainError> {
+    metrics::encode()
+}
+
+#[derive(Debug, Serialize)]
+struct Readiness {
+    ready: bool,
+    checks: BTreeMap<&'static str, String>,
+}
+
+async fn readyz(State(state): State<Arc<AdminState>>) -> Response {
+    let mut readiness = Readiness {
+        ready: true,
+        checks: BTreeMap::new(),
+    };
+
+    for (name, database) in &state.databases {
+        let check = match database.ping().await {
+            Ok(()) => "ok".to_string(),
+            Err(err) => {
+                readiness.ready = false;
+                err.to_string()
+            }
+        };
+        readiness.checks.insert(name, check);
+    }
+
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(readiness)).into_response()
+}
+
+#[derive(Debug, Serialize)]
+struct JobStatus {
+    job: &'static str,
+    last_run: Option<RunStatus>,
+}
+
+#[derive(Debug, Serialize)]
+struct RunStatus {
+    sync_runid: i32,
+    status: &'static str,
+    inserted: Option<i32>,
+    updated: Option<i32>,
+    unchanged: Option<i32>,
+    deleted: Option<i32>,
+    error: Option<String>,
+    started_at: String,
+    finished_at: Option<String>,
+}
+
+impl From<SyncRunModel> for RunStatus {
+    fn from(sync_run: SyncRunModel) -> Self {
+        Self {
+            sync_runid: sync_run.sync_runid,
+            status: sync_run.status.as_str(),
+            inserted: sync_run.report.map(|report| report.inserted),
+            updated: sync_run.report.map(|report| report.updated),
+            unchanged: sync_run.report.map(|report| report.unchanged),
+            deleted: sync_run.report.map(|report| report.deleted),
+            error: sync_run.error,
+            started_at: sync_run.started_at.to_rfc3339(),
+            finished_at: sync_run.finished_at.map(|finished_at| finished_at.to_rfc3339()),
+        }
+    }
+}
+
+async fn jobs(State(state): State<Arc<AdminState>>) -> Result<Json<Vec<JobStatus>>, DomainError> {
+    let mut statuses = Vec::new();
+
+    for job in &state.jobs {
+        let last_run = find::execute(state.sync_run_repository.clone(), Some(job.name().to_string()), 1, 1)
+            .await?
+            .and_then(|(sync_runs, _)| sync_runs.into_iter().next());
+
+        statuses.push(JobStatus {
+            job: job.name(),
+            last_run: last_run.map(RunStatus::from),
+        });
+    }
+
+    Ok(Json(statuses))
+}
+
+#[derive(Debug, Serialize)]
+struct RunAccepted {
+    job: &'static str,
+}
+
+async fn run_job(State(state): State<Arc<AdminState>>, Path(name): Path<String>) -> Result<Response, DomainError> {
+    let job = state
+        .jobs
+        .iter()
+        .find(|job| job.name() == name)
+        .cloned()
+        .ok_or_else(|| DomainError::NotFound(format!("Job '{}' not found", name)))?;
+
+    let supervisor = state.supervisor.clone();
+    let accepted = RunAccepted { job: job.name() };
+
+    let mut runs = state.runs.lock().await;
+    // Forget the runs that are over
+    while runs.try_join_next().is_some() {}
+
+    runs.spawn(async move {
+        if let Err(err) = supervisor.run_with_retries(job.clone()).await {
+            tracing::error!("{} sync triggered from the admin server failed: {}", job.name(), err);
+        }
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(accepted)).into_response())
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            DomainError::NotFound(_) => StatusCode::NOT_FOUND,
+            DomainError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            DomainError::InternalServerError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, Json(ErrorResponse { error: self.to_string() })).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use axum::{body::Body, http::Request};
+    use http_body_util::BodyExt;
+    use mockall::mock;
+    use tower::ServiceExt;
+
+    use crate::{
+        api::{
+            lib::SyncContext,
+            supervisor::{LockMode, RetryPolicy},
+        },
+        domain::{
+            sync_lock::repository::{SyncLock, SyncLockRepository},
+            sync_run::model::{SyncReport, SyncRunCreateModel, SyncRunStatus, SyncRunUpdateModel},
+        },
+    };
+
+    mock! {
+        pub FakeHealthRepository { }
+
+        #[async_trait]
+        impl HealthRepository for FakeHealthRepository {
+            async fn ping(&self) -> Result<(), DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeSyncRunRepository { }
+
+        #[async_trait]
+        impl SyncRunRepository for FakeSyncRunRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+            async fn insert(&self,sync_run_create_model: &SyncRunCreateModel) -> Result<SyncRunModel, DomainError>;
+            async fn update_by_id(&self,id: &i32,sync_run_update_model: &SyncRunUpdateModel) -> Result<SyncRunModel, DomainError>;
+            async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeSyncLockRepository { }
+
+        #[async_trait]
+        impl SyncLockRepository for FakeSyncLockRepository {
+            async fn try_acquire(&self, job: &str) -> Result<Option<Box<dyn SyncLock>>, DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeSyncJob { }
+
+        #[async_trait]
+        impl SyncJob for FakeSyncJob {
+            fn name(&self) -> &'static str;
+            async fn sync(&self, context: &SyncContext) -> Result<SyncReport, DomainError>;
+        }
+    }
+
+    fn healthy(healthy: bool) -> Arc<dyn HealthRepository> {
+        let mut health_repository = MockFakeHealthRepository::new();
+        health_repository.expect_ping().returning(move || match healthy {
+            true => Ok(()),
+            false => Err(DomainError::InternalServerError("connection refused".to_string())),
+        });
+        Arc::new(health_repository)
+    }
+
+    fn articles_job() -> Arc<dyn SyncJob> {
+        let mut job = MockFakeSyncJob::new();
+        job.expect_name().return_const("articles");
+        job.expect_sync().returning(|_| Ok(SyncReport::default()));
+        Arc::new(job)
+    }
+
+    fn state(databases: Vec<(&'static str, Arc<dyn HealthRepository>)>, sync_run_repository: MockFakeSyncRunRepository) -> Arc<AdminState> {
+        // Runs triggered in tests are skipped, as if another instance held the lock
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository.expect_try_acquire().returning(|_| Ok(None));
+
+        let sync_run_repository = Arc::new(sync_run_repository);
+        let supervisor = Supervisor::new(
+            sync_run_repository.clone(),
+            Arc::new(sync_lock_repository),
+            RetryPolicy {
+                max_retries: 0,
+                initial_backoff: Duration::ZERO,
+                max_backoff: Duration::ZERO,
+            },
+            Duration::ZERO,
+            LockMode::Skip,
+            Shutdown::manual(Duration::ZERO).1,
+        );
+
+        Arc::new(AdminState::new(databases, sync_run_repository, vec![articles_job()], supervisor))
+    }
+
+    async fn send(state: Arc<AdminState>, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = router(state)
+            .oneshot(Request::builder().method(method).uri(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+
+        (status, serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null))
+    }
+
+    #[tokio::test]
+    async fn it_should_return_ready_databases_up() {
+        let state = state(
+            vec![("database", healthy(true)), ("sync_database", healthy(true))],
+            MockFakeSyncRunRepository::new(),
+        );
+
+        let (status, body) = send(state, "GET", "/readyz").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["checks"]["sync_database"], "ok");
+    }
+
+    #[tokio::test]
+    async fn it_should_return_unavailable_database_down() {
+        let state = state(
+            vec![("database", healthy(true)), ("sync_database", healthy(false))],
+            MockFakeSyncRunRepository::new(),
+        );
+
+        let (status, body) = send(state, "GET", "/readyz").await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["ready"], false);
+        assert_eq!(body["checks"]["database"], "ok");
+        assert_eq!(body["checks"]["sync_database"], "connection refused");
+    }
+
+    #[tokio::test]
+    async fn it_should_return_metrics() {
+        let response = router(state(vec![], MockFakeSyncRunRepository::new()))
+            .oneshot(Request::builder().uri("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_last_run_per_job() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+        sync_run_repository
+            .expect_find()
+            .withf(|job, page, page_size| job.as_deref() == Some("articles") && *page == 1 && *page_size == 1)
+            .returning(|_, _, _| {
+                Ok(Some((
+                    vec![SyncRunModel {
+                        status: SyncRunStatus::Succeeded,
+                        report: Some(SyncReport {
+                            inserted: 4,
+                            updated: 1,
+                            unchanged: 20,
+                            deleted: 0,
+                        }),
+                        ..SyncRunModel::mock_default()
+                    }],
+                    1,
+                )))
+            });
+
+        let (status, body) = send(state(vec![], sync_run_repository), "GET", "/jobs").await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body[0]["job"], "articles");
+        assert_eq!(body[0]["last_run"]["status"], "succeeded");
+        assert_eq!(body[0]["last_run"]["inserted"], 4);
+    }
+
+    #[tokio::test]
+    async fn it_should_accept_run_of_known_job() {
+        let (status, body) = send(state(vec![], MockFakeSyncRunRepository::new()), "POST", "/jobs/articles/run").await;
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(body["job"], "articles");
+    }
+
+    #[tokio::test]
+    async fn it_should_return_not_found_unknown_job() {
+        let (status, _) = send(state(vec![], MockFakeSyncRunRepository::new()), "POST", "/jobs/cities/run").await;
+
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,274 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use super::lib::Force;
+
+#[derive(Debug, Parser)]
+#[command(name = "sync", about = "Synchronizes Medium and Meetup data into the dataroot.ca database")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the scheduler until stopped (default)
+    Daemon,
+    /// Run a single sync pass and exit
+    Run {
+        #[arg(value_enum)]
+        job: JobArg,
+        /// Report what would be inserted or updated instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+        /// Format of the dry run report
+        #[arg(long, value_enum, default_value_t = ReportFormat::Human, requires = "dry_run")]
+        format: ReportFormat,
+        #[command(flatten)]
+        force: ForceArgs,
+    },
+    /// Manage the registered authors whose articles are synced
+    Authors {
+        #[command(subcommand)]
+        command: RegistryCommand,
+    },
+    /// Manage the registered groups whose details and events are synced
+    Groups {
+        #[command(subcommand)]
+        command: RegistryCommand,
+    },
+    /// Show the fields the syncs changed on an item, oldest first
+    History {
+        #[arg(value_enum)]
+        entity: EntityArg,
+        extid: String,
+    },
+    /// Inspect and retry the items that failed to fetch
+    DeadLetters {
+        #[command(subcommand)]
+        command: DeadLetterCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RegistryCommand {
+    /// Register a new entry
+    Add {
+        name: String,
+        /// Defaults to medium for authors and meetup for groups
+        #[arg(long)]
+        source: Option<String>,
+        /// RSS or Atom feed of an author, required by the feed source
+        #[arg(long)]
+        feed_url: Option<String>,
+    },
+    /// List registered entries
+    List {
+        #[arg(long)]
+        source: Option<String>,
+    },
+    /// Remove an entry by id
+    Remove {
+        id: i32,
+    },
+    /// Register every entry of a CSV (name,source[,feed_url]) or YAML file, skipping the ones already registered
+    Import {
+        file: PathBuf,
+        /// Source of the entries that don't set one
+        #[arg(long)]
+        source: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DeadLetterCommand {
+    /// List the items that failed to fetch
+    List {
+        #[arg(long)]
+        job: Option<String>,
+    },
+    /// Show a dead letter with the payload excerpt that failed
+    Show {
+        id: i32,
+    },
+    /// Rerun the author or group of a dead letter as a forced pass
+    Retry {
+        id: i32,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct ForceArgs {
+    /// Rewrite every fetched item, even when its checksum did not change
+    #[arg(long)]
+    pub force: bool,
+    /// Only force the authors or groups of this source
+    #[arg(long, requires = "force")]
+    pub source: Option<String>,
+    /// Only force the articles of this author
+    #[arg(long, requires = "force", conflicts_with = "group")]
+    pub author: Option<String>,
+    /// Only force this group and its events, by slug
+    #[arg(long, requires = "force")]
+    pub group: Option<String>,
+}
+
+impl ForceArgs {
+    pub fn to_force(&self) -> Option<Force> {
+        self.force.then(|| Force {
+            source: self.source.clone(),
+            author: self.author.clone(),
+            group: self.group.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ReportFormat {
+    Human,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum EntityArg {
+    Article,
+    Group,
+    Event,
+}
+
+impl EntityArg {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EntityArg::Article => "article",
+            EntityArg::Group => "group",
+            EntityArg::Event => "event",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum JobArg {
+    Articles,
+    Groups,
+    Events,
+    All,
+}
+
+impl JobArg {
+    pub fn names(&self) -> Vec<&'static str> {
+        match self {
+            JobArg::Articles => vec!["articles"],
+            JobArg::Groups => vec!["groups"],
+            JobArg::Events => vec!["events"],
+            JobArg::All => vec!["articles", "groups", "events"],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_run_job() {
+        let cli = Cli::try_parse_from(["sync", "run", "all"]).unwrap();
+
+        match cli.command {
+            Some(Command::Run { job, dry_run, .. }) => {
+                assert_eq!(job.names(), vec!["articles", "groups", "events"]);
+                assert!(!dry_run);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_parse_dry_run_format() {
+        let cli = Cli::try_parse_from(["sync", "run", "articles", "--dry-run", "--format", "json"]).unwrap();
+
+        match cli.command {
+            Some(Command::Run { dry_run, format, .. }) => {
+                assert!(dry_run);
+                assert_eq!(format, ReportFormat::Json);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_error_format_without_dry_run() {
+        assert!(Cli::try_parse_from(["sync", "run", "articles", "--format", "json"]).is_err());
+    }
+
+    #[test]
+    fn it_should_default_to_daemon() {
+        let cli = Cli::try_parse_from(["sync"]).unwrap();
+
+        assert!(cli.command.is_none());
+    }
+
+    #[test]
+    fn it_should_parse_registry_command() {
+        let cli = Cli::try_parse_from(["sync", "authors", "add", "kozyrkov", "--source", "medium"]).unwrap();
+
+        match cli.command {
+            Some(Command::Authors { command: RegistryCommand::Add { name, source, feed_url } }) => {
+                assert_eq!(name, "kozyrkov");
+                assert_eq!(source.as_deref(), Some("medium"));
+                assert!(feed_url.is_none());
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_parse_force_filter() {
+        let cli = Cli::try_parse_from(["sync", "run", "events", "--force", "--group", "rust-montreal"]).unwrap();
+
+        match cli.command {
+            Some(Command::Run { force, .. }) => assert_eq!(
+                force.to_force(),
+                Some(Force {
+                    source: None,
+                    author: None,
+                    group: Some("rust-montreal".to_string()),
+                })
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_error_filter_without_force() {
+        assert!(Cli::try_parse_from(["sync", "run", "articles", "--author", "kozyrkov"]).is_err());
+    }
+
+    #[test]
+    fn it_should_parse_dead_letter_retry() {
+        let cli = Cli::try_parse_from(["sync", "dead-letters", "retry", "42"]).unwrap();
+
+        match cli.command {
+            Some(Command::DeadLetters { command: DeadLetterCommand::Retry { id } }) => assert_eq!(id, 42),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_parse_history_entity() {
+        let cli = Cli::try_parse_from(["sync", "history", "event", "m297517437"]).unwrap();
+
+        match cli.command {
+            Some(Command::History { entity, extid }) => {
+                assert_eq!(entity.name(), "event");
+                assert_eq!(extid, "m297517437");
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_error_unknown_job() {
+        assert!(Cli::try_parse_from(["sync", "run", "cities"]).is_err());
+    }
+}
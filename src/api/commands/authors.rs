@@ -0,0 +1,101 @@
+use std::sync::Arc;
+
+use crate::{api::cli::RegistryCommand, domain::{
+    error::DomainError,
+    registered_author::{
+        model::{RegisteredAuthorCreateModel, RegisteredAuthorModel},
+        repository::RegisteredAuthorRepository,
+        resources::{create, delete_by_id, find},
+    },
+}};
+
+use super::{check_source, read_entries, PAGE_SIZE};
+
+const DEFAULT_SOURCE: &str = "medium";
+
+// Source of the authors only known by their feed URL
+const FEED_SOURCE: &str = "feed";
+
+fn new_author(name: String, source: String, feed_url: Option<String>, sources: &[String]) -> Result<RegisteredAuthorCreateModel, DomainError> {
+    check_source(&source, sources)?;
+    if source == FEED_SOURCE && feed_url.is_none() {
+        return Err(DomainError::BadRequest(format!("Author {} of the {} source needs a feed URL", name, FEED_SOURCE)));
+    }
+    Ok(RegisteredAuthorCreateModel::new(name, source, feed_url))
+}
+
+pub async fn execute(
+    registered_author_repository: Arc<dyn RegisteredAuthorRepository>,
+    sources: &[String],
+    command: RegistryCommand,
+) -> Result<(), DomainError> {
+    match command {
+        RegistryCommand::Add { name, source, feed_url } => {
+            let source = source.unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+            let author = create::execute(registered_author_repository, new_author(name, source, feed_url, sources)?).await?;
+            println!("Registered author {} ({}) with id {}", author.name, author.source, author.registered_authorid);
+        }
+        RegistryCommand::List { source } => {
+            for author in find_all(registered_author_repository).await? {
+                if source.is_none() || source.as_ref() == Some(&author.source) {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        author.registered_authorid,
+                        author.source,
+                        author.name,
+                        author.feed_url.as_deref().unwrap_or_default()
+                    );
+                }
+            }
+        }
+        RegistryCommand::Remove { id } => {
+            delete_by_id::execute(registered_author_repository, id).await?;
+            println!("Removed author {}", id);
+        }
+        RegistryCommand::Import { file, source } => {
+            let default_source = source.unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+            let mut registered: Vec<(String, String)> = find_all(registered_author_repository.clone())
+                .await?
+                .into_iter()
+                .map(|author| (author.name, author.source))
+                .collect();
+
+            let mut imported = 0;
+            let mut skipped = 0;
+            for entry in read_entries(&file)? {
+                let key = (entry.name, entry.source.unwrap_or_else(|| default_source.clone()));
+                if registered.contains(&key) {
+                    skipped += 1;
+                    continue;
+                }
+
+                create::execute(
+                    registered_author_repository.clone(),
+                    new_author(key.0.clone(), key.1.clone(), entry.feed_url, sources)?,
+                ).await?;
+                registered.push(key);
+                imported += 1;
+            }
+            println!("Imported {} authors, skipped {} already registered", imported, skipped);
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_all(
+    registered_author_repository: Arc<dyn RegisteredAuthorRepository>,
+) -> Result<Vec<RegisteredAuthorModel>, DomainError> {
+    let mut authors = Vec::new();
+    let mut page = 1;
+
+    while let Some((items, total)) = find::execute(registered_author_repository.clone(), None, page, PAGE_SIZE).await? {
+        authors.extend(items);
+        if authors.len() as u32 >= total {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(authors)
+}
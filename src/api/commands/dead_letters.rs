@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use crate::{api::{cli::DeadLetterCommand, lib::{Force, Scheduler}}, domain::{
+    dead_letter::{
+        model::DeadLetterModel,
+        repository::DeadLetterRepository,
+        resources::{find, find_by_id},
+    },
+    error::DomainError,
+}};
+
+use super::PAGE_SIZE;
+
+pub async fn execute(
+    dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    scheduler: &Scheduler,
+    command: DeadLetterCommand,
+) -> Result<(), DomainError> {
+    match command {
+        DeadLetterCommand::List { job } => {
+            let mut page = 1;
+            let mut listed = 0;
+            while let Some((items, total)) = find::execute(dead_letter_repository.clone(), job.clone(), page, PAGE_SIZE).await? {
+                listed += items.len() as u32;
+                for item in items {
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+                        item.sync_dead_letterid, item.job, item.source, item.scope, item.key, item.attempts, item.error
+                    );
+                }
+                if listed >= total {
+                    break;
+                }
+                page += 1;
+            }
+        }
+        DeadLetterCommand::Show { id } => {
+            let dead_letter = find_by_id::execute(dead_letter_repository, id).await?;
+            println!("id: {}", dead_letter.sync_dead_letterid);
+            println!("job: {}", dead_letter.job);
+            println!("source: {}", dead_letter.source);
+            println!("scope: {}", dead_letter.scope);
+            println!("key: {}", dead_letter.key);
+            println!("error: {}", dead_letter.error);
+            println!("attempts: {}", dead_letter.attempts);
+            println!("first failed: {}", dead_letter.created_at);
+            if let Some(updated_at) = dead_letter.updated_at {
+                println!("last failed: {}", updated_at);
+            }
+            if let Some(payload) = dead_letter.payload {
+                println!("payload:\n{}", payload);
+            }
+        }
+        DeadLetterCommand::Retry { id } => {
+            let dead_letter = find_by_id::execute(dead_letter_repository.clone(), id).await?;
+            let force = retry_force(&dead_letter)?;
+            let job = dead_letter.job.as_str();
+
+            scheduler.run_once(&[job], Some(force)).await?;
+
+            match find_by_id::execute(dead_letter_repository, id).await {
+                Err(DomainError::NotFound(_)) => println!("Dead letter {} resolved", id),
+                Err(err) => return Err(err),
+                Ok(dead_letter) => {
+                    return Err(DomainError::InternalServerError(format!(
+                        "Dead letter {} failed again: {}",
+                        id, dead_letter.error
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Forced pass over the author or group the dead letter failed in.
+fn retry_force(dead_letter: &DeadLetterModel) -> Result<Force, DomainError> {
+    let scope = Some(dead_letter.scope.clone());
+    let (author, group) = match dead_letter.job.as_str() {
+        "articles" => (scope, None),
+        "groups" | "events" => (None, scope),
+        job => return Err(DomainError::BadRequest(format!("Unknown job {}", job))),
+    };
+
+    Ok(Force {
+        source: Some(dead_letter.source.clone()),
+        author,
+        group,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_force_scope_of_dead_letter() {
+        let mut dead_letter = DeadLetterModel::mock_default();
+
+        assert_eq!(retry_force(&dead_letter).unwrap(), Force {
+            source: Some("medium".to_string()),
+            author: Some("kozyrkov".to_string()),
+            group: None,
+        });
+
+        dead_letter.job = "cities".to_string();
+        assert!(matches!(retry_force(&dead_letter), Err(DomainError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use crate::{api::cli::RegistryCommand, domain::{
+    error::DomainError,
+    registered_group::{
+        model::{RegisteredGroupCreateModel, RegisteredGroupModel},
+        repository::RegisteredGroupRepository,
+        resources::{create, delete_by_id, find},
+    },
+}};
+
+use super::{check_source, read_entries, PAGE_SIZE};
+
+const DEFAULT_SOURCE: &str = "meetup";
+
+pub async fn execute(
+    registered_group_repository: Arc<dyn RegisteredGroupRepository>,
+    sources: &[String],
+    command: RegistryCommand,
+) -> Result<(), DomainError> {
+    match command {
+        RegistryCommand::Add { feed_url: Some(_), .. } => {
+            return Err(DomainError::BadRequest("Only authors have a feed URL".to_string()));
+        }
+        RegistryCommand::Add { name, source, .. } => {
+            let source = source.unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+            check_source(&source, sources)?;
+            let group = create::execute(registered_group_repository, RegisteredGroupCreateModel::new(name, source)).await?;
+            println!("Registered group {} ({}) with id {}", group.name, group.source, group.registered_groupid);
+        }
+        RegistryCommand::List { source } => {
+            for group in find_all(registered_group_repository).await? {
+                if source.is_none() || source.as_ref() == Some(&group.source) {
+                    println!("{}\t{}\t{}", group.registered_groupid, group.source, group.name);
+                }
+            }
+        }
+        RegistryCommand::Remove { id } => {
+            delete_by_id::execute(registered_group_repository, id).await?;
+            println!("Removed group {}", id);
+        }
+        RegistryCommand::Import { file, source } => {
+            let default_source = source.unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+            let mut registered: Vec<(String, String)> = find_all(registered_group_repository.clone())
+                .await?
+                .into_iter()
+                .map(|group| (group.name, group.source))
+                .collect();
+
+            let mut imported = 0;
+            let mut skipped = 0;
+            for entry in read_entries(&file)? {
+                let key = (entry.name, entry.source.unwrap_or_else(|| default_source.clone()));
+                if registered.contains(&key) {
+                    skipped += 1;
+                    continue;
+                }
+
+                check_source(&key.1, sources)?;
+                create::execute(
+                    registered_group_repository.clone(),
+                    RegisteredGroupCreateModel::new(key.0.clone(), key.1.clone()),
+                ).await?;
+                registered.push(key);
+                imported += 1;
+            }
+            println!("Imported {} groups, skipped {} already registered", imported, skipped);
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_all(
+    registered_group_repository: Arc<dyn RegisteredGroupRepository>,
+) -> Result<Vec<RegisteredGroupModel>, DomainError> {
+    let mut groups = Vec::new();
+    let mut page = 1;
+
+    while let Some((items, total)) = find::execute(registered_group_repository.clone(), None, page, PAGE_SIZE).await? {
+        groups.extend(items);
+        if groups.len() as u32 >= total {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(groups)
+}
@@ -0,0 +1,34 @@
+use std::sync::Arc;
+
+use crate::{api::cli::EntityArg, domain::{
+    error::DomainError,
+    field_change::{repository::FieldChangeRepository, resources::find_by_extid},
+}};
+
+// Printed for a run id or a value that is not set
+const UNSET: &str = "-";
+
+pub async fn execute(
+    field_change_repository: Arc<dyn FieldChangeRepository>,
+    entity: EntityArg,
+    extid: &str,
+) -> Result<(), DomainError> {
+    let changes = find_by_extid::execute(field_change_repository, entity.name(), extid).await?;
+    if changes.is_empty() {
+        println!("No change recorded for {} {}", entity.name(), extid);
+    }
+
+    for change in changes {
+        println!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            change.sync_field_changeid,
+            change.changed_at.to_rfc3339(),
+            change.run_id.map_or_else(|| UNSET.to_string(), |run_id| run_id.to_string()),
+            change.field,
+            change.before.as_deref().unwrap_or(UNSET),
+            change.after.as_deref().unwrap_or(UNSET),
+        );
+    }
+
+    Ok(())
+}
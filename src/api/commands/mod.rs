@@ -0,0 +1,104 @@
+pub mod authors;
+pub mod dead_letters;
+pub mod groups;
+pub mod history;
+
+use std::{fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::domain::error::DomainError;
+
+const PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ImportEntry {
+    pub name: String,
+    pub source: Option<String>,
+    #[serde(default)]
+    pub feed_url: Option<String>,
+}
+
+/// Rejects a `source` without adapter, whose authors or groups would fail
+/// every run.
+pub fn check_source(source: &str, sources: &[String]) -> Result<(), DomainError> {
+    if !sources.iter().any(|known| known == source) {
+        return Err(DomainError::BadRequest(format!("Unknown source '{}', expected one of {}", source, sources.join(", "))));
+    }
+    Ok(())
+}
+
+/// Reads `name,source[,feed_url]` entries from a CSV file (with a header row) or a YAML list,
+/// picked by the file extension.
+pub fn read_entries(path: &Path) -> Result<Vec<ImportEntry>, DomainError> {
+    let content = fs::read_to_string(path)
+        .map_err(|err| DomainError::BadRequest(format!("Failed to read {}: {}", path.display(), err)))?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => parse_csv(&content),
+        Some("yaml") | Some("yml") => parse_yaml(&content),
+        _ => Err(DomainError::BadRequest(format!("Unsupported import file {}, expected .csv, .yaml or .yml", path.display()))),
+    }
+}
+
+fn parse_csv(content: &str) -> Result<Vec<ImportEntry>, DomainError> {
+    csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes())
+        .deserialize()
+        .collect::<Result<Vec<ImportEntry>, _>>()
+        .map_err(|err| DomainError::BadRequest(format!("Invalid CSV: {}", err)))
+}
+
+fn parse_yaml(content: &str) -> Result<Vec<ImportEntry>, DomainError> {
+    serde_yaml::from_str(content).map_err(|err| DomainError::BadRequest(format!("Invalid YAML: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_parse_csv_entries() {
+        let entries = parse_csv("name,source\nkozyrkov,medium\n barrmoses , \n").unwrap();
+
+        assert_eq!(entries, vec![
+            ImportEntry { name: "kozyrkov".to_string(), source: Some("medium".to_string()), feed_url: None },
+            ImportEntry { name: "barrmoses".to_string(), source: None, feed_url: None },
+        ]);
+    }
+
+    #[test]
+    fn it_should_parse_yaml_entries() {
+        let entries = parse_yaml("- name: kozyrkov\n  source: medium\n- name: barrmoses\n").unwrap();
+
+        assert_eq!(entries, vec![
+            ImportEntry { name: "kozyrkov".to_string(), source: Some("medium".to_string()), feed_url: None },
+            ImportEntry { name: "barrmoses".to_string(), source: None, feed_url: None },
+        ]);
+    }
+
+    #[test]
+    fn it_should_parse_feed_url() {
+        let entries = parse_csv("name,source,feed_url\nlab,feed,https://lab.example.ca/feed.xml\n").unwrap();
+
+        assert_eq!(entries[0].feed_url.as_deref(), Some("https://lab.example.ca/feed.xml"));
+    }
+
+    #[test]
+    fn it_should_return_error_unknown_source() {
+        let sources = vec!["medium".to_string(), "feed".to_string()];
+
+        assert!(check_source("medium", &sources).is_ok());
+        match check_source("tumblr", &sources) {
+            Err(DomainError::BadRequest(message)) => assert_eq!(message, "Unknown source 'tumblr', expected one of medium, feed"),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn it_should_return_error_invalid_file() {
+        assert!(matches!(parse_csv("source\nmedium\n"), Err(DomainError::BadRequest(_))));
+        assert!(matches!(read_entries(Path::new("authors.json")), Err(DomainError::BadRequest(_))));
+    }
+}
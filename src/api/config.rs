@@ -1,5 +1,15 @@
+use chrono_tz::Tz;
 use lazy_static::lazy_static;
+use std::collections::HashMap;
 use std::env;
+use std::time::Duration;
+
+use crate::domain::error::DomainError;
+
+use super::{schedule::{parse_timezone, Schedule}, supervisor::{LockMode, RetryPolicy}};
+
+const SCHEDULE_ENV_PREFIX: &str = "SYNC_SCHEDULE_";
+const DELETE_AFTER_RUNS_ENV_PREFIX: &str = "SYNC_DELETE_AFTER_RUNS_";
 
 lazy_static! {
     static ref CONFIG: Config = Config::from_env();
@@ -11,23 +21,92 @@ pub fn get_config() -> &'static Config {
 
 #[derive(Debug, Clone)]
 pub struct Config {
-    pub web_addr: String,
-    pub page_size_default: u32,
-    pub page_size_max: u32,
+    pub web_addr: Option<String>,
+    pub timezone: Tz,
+    pub schedules: HashMap<String, String>,
+    pub delete_after_runs: HashMap<String, i32>,
+    pub retry_policy: RetryPolicy,
+    pub catch_up_grace: Duration,
+    pub lock_mode: LockMode,
+    pub shutdown_timeout: Duration,
 }
 
 impl Config {
     fn from_env() -> Self {
         Self {
-            web_addr: env::var("ADDR").expect("ADDR must be set"),
-            page_size_default: env::var("PAGE_SIZE_DEFAULT")
-                .expect("PAGE_SIZE_DEFAULT must be set")
+            web_addr: env::var("ADDR").ok(),
+            timezone: parse_timezone(&env::var("SYNC_TIMEZONE").unwrap_or_else(|_| "UTC".to_string()))
+                .expect("SYNC_TIMEZONE must be an IANA timezone"),
+            schedules: env::vars()
+                .filter_map(|(key, value)| {
+                    key.strip_prefix(SCHEDULE_ENV_PREFIX)
+                        .map(|job| (job.to_lowercase(), value))
+                })
+                .collect(),
+            delete_after_runs: env::vars()
+                .filter_map(|(key, value)| {
+                    key.strip_prefix(DELETE_AFTER_RUNS_ENV_PREFIX).map(|job| {
+                        let runs = value
+                            .parse()
+                            .unwrap_or_else(|_| panic!("{}{} must be i32", DELETE_AFTER_RUNS_ENV_PREFIX, job));
+                        (job.to_lowercase(), runs)
+                    })
+                })
+                .collect(),
+            retry_policy: RetryPolicy {
+                max_retries: env::var("SYNC_RETRY_ATTEMPTS")
+                    .unwrap_or_else(|_| "0".to_string())
+                    .parse()
+                    .expect("SYNC_RETRY_ATTEMPTS must be u32"),
+                initial_backoff: Duration::from_secs(
+                    env::var("SYNC_RETRY_BACKOFF_SECS")
+                        .unwrap_or_else(|_| "60".to_string())
+                        .parse()
+                        .expect("SYNC_RETRY_BACKOFF_SECS must be u64"),
+                ),
+                max_backoff: Duration::from_secs(
+                    env::var("SYNC_RETRY_BACKOFF_MAX_SECS")
+                        .unwrap_or_else(|_| "1800".to_string())
+                        .parse()
+                        .expect("SYNC_RETRY_BACKOFF_MAX_SECS must be u64"),
+                ),
+            },
+            catch_up_grace: Duration::from_secs(
+                env::var("SYNC_CATCH_UP_GRACE_SECS")
+                    .unwrap_or_else(|_| "86400".to_string())
+                    .parse()
+                    .expect("SYNC_CATCH_UP_GRACE_SECS must be u64"),
+            ),
+            lock_mode: env::var("SYNC_LOCK_MODE")
+                .unwrap_or_else(|_| "skip".to_string())
                 .parse()
-                .expect("PAGE_SIZE_DEFAULT must be u32"),
-            page_size_max: env::var("PAGE_SIZE_MAX")
-                .expect("PAGE_SIZE_MAX must be set")
-                .parse::<u32>()
-                .expect("PAGE_SIZE_MAX must be u32"),
+                .expect("SYNC_LOCK_MODE must be skip or wait"),
+            shutdown_timeout: Duration::from_secs(
+                env::var("SYNC_SHUTDOWN_TIMEOUT_SECS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .expect("SYNC_SHUTDOWN_TIMEOUT_SECS must be u64"),
+            ),
         }
     }
+
+    /// Address the admin API listens on, from `ADDR`. Only the daemon serves
+    /// it, so the other commands run without it.
+    pub fn web_addr(&self) -> Result<&str, DomainError> {
+        self.web_addr
+            .as_deref()
+            .ok_or_else(|| DomainError::BadRequest("ADDR must be set to serve the admin API".to_string()))
+    }
+
+    /// Schedule of a job from `SYNC_SCHEDULE_<JOB>`, falling back to `default`.
+    pub fn schedule(&self, job: &str, default: &str) -> Result<Schedule, DomainError> {
+        let expression = self.schedules.get(job).map(String::as_str).unwrap_or(default);
+        Schedule::parse(expression, self.timezone)
+    }
+
+    /// Complete runs an item of `job` may be missing from its source before
+    /// it is deleted, from `SYNC_DELETE_AFTER_RUNS_<JOB>`; 0 never deletes.
+    pub fn delete_after_runs(&self, job: &str) -> i32 {
+        self.delete_after_runs.get(job).copied().unwrap_or(0)
+    }
 }
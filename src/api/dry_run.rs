@@ -0,0 +1,157 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::domain::{error::DomainError, field_change::model::FieldChange};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlannedAction {
+    Insert,
+    Update,
+}
+
+/// Write a sync would have made to one item.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedChange {
+    pub entity: String,
+    pub extid: String,
+    pub action: PlannedAction,
+    pub fields: Vec<FieldChange>,
+}
+
+/// Everything a dry run would have written, in the order it was found.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DryRunReport {
+    pub changes: Vec<PlannedChange>,
+}
+
+impl DryRunReport {
+    pub fn push(&mut self, change: PlannedChange) {
+        self.changes.push(change);
+    }
+
+    pub fn extend(&mut self, other: DryRunReport) {
+        self.changes.extend(other.changes);
+    }
+
+    pub fn to_json(&self) -> Result<String, DomainError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| DomainError::InternalServerError(format!("Failed to serialize dry run report: {}", err)))
+    }
+
+    fn count(&self, entity: &str, action: PlannedAction) -> usize {
+        self.changes
+            .iter()
+            .filter(|change| change.entity == entity && change.action == action)
+            .count()
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changes.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        let mut entities: Vec<&str> = Vec::new();
+        for change in &self.changes {
+            if !entities.contains(&change.entity.as_str()) {
+                entities.push(&change.entity);
+            }
+        }
+
+        for entity in entities {
+            writeln!(
+                f,
+                "{}: {} to insert, {} to update",
+                entity,
+                self.count(entity, PlannedAction::Insert),
+                self.count(entity, PlannedAction::Update)
+            )?;
+
+            for change in self.changes.iter().filter(|change| change.entity == entity) {
+                match change.action {
+                    PlannedAction::Insert => writeln!(f, "  + {}", change.extid)?,
+                    PlannedAction::Update => writeln!(f, "  ~ {}", change.extid)?,
+                }
+
+                for field in &change.fields {
+                    match change.action {
+                        PlannedAction::Insert => writeln!(f, "      {}: {}", field.field, display_value(&field.after))?,
+                        PlannedAction::Update => writeln!(
+                            f,
+                            "      {}: {} -> {}",
+                            field.field,
+                            display_value(&field.before),
+                            display_value(&field.after)
+                        )?,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn display_value(value: &Option<String>) -> String {
+    match value {
+        Some(value) => format!("{:?}", value),
+        None => "null".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> DryRunReport {
+        DryRunReport {
+            changes: vec![
+                PlannedChange {
+                    entity: "article".to_string(),
+                    extid: "a1".to_string(),
+                    action: PlannedAction::Insert,
+                    fields: vec![FieldChange {
+                        field: "name".to_string(),
+                        before: None,
+                        after: Some("Hello".to_string()),
+                    }],
+                },
+                PlannedChange {
+                    entity: "article".to_string(),
+                    extid: "a2".to_string(),
+                    action: PlannedAction::Update,
+                    fields: vec![FieldChange {
+                        field: "time_m".to_string(),
+                        before: Some("4".to_string()),
+                        after: Some("5".to_string()),
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn it_should_display_changes_per_entity() {
+        assert_eq!(
+            report().to_string(),
+            "article: 1 to insert, 1 to update\n  + a1\n      name: \"Hello\"\n  ~ a2\n      time_m: \"4\" -> \"5\"\n"
+        );
+    }
+
+    #[test]
+    fn it_should_serialize_changes_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json().unwrap()).unwrap();
+
+        assert_eq!(json["changes"][0]["action"], "insert");
+        assert_eq!(json["changes"][1]["fields"][0]["before"], "4");
+        assert_eq!(json["changes"][1]["fields"][0]["after"], "5");
+    }
+
+    #[test]
+    fn it_should_display_no_changes() {
+        assert_eq!(DryRunReport::default().to_string(), "No changes\n");
+    }
+}
@@ -0,0 +1,926 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+
+use tracing::Instrument;
+
+use crate::{
+    domain::{
+        article::model::{Guidable, Processable},
+        dead_letter::{
+            model::{DeadLetterCreateModel, FetchFailure},
+            repository::DeadLetterRepository,
+        },
+        error::DomainError,
+        field_change::{
+            model::{Diffable, FieldChange, FieldChangeCreateModel},
+            repository::FieldChangeRepository,
+        },
+        sync_run::model::SyncReport,
+    },
+    infrastructure::metrics,
+};
+
+use super::{
+    dry_run::{PlannedAction, PlannedChange},
+    lib::{BatchOperations, CurrentOperations, DiffOperations, SyncContext},
+};
+
+/// A fetched item the engine can diff against the sync database and write.
+pub trait SyncItem: Processable + Guidable + Clone + Send + Sync {
+    /// Model written to update the stored row.
+    type Update: Diffable<Self::Current> + Send + Sync;
+    /// Row stored in the main database.
+    type Current: Send + Sync;
+    /// Row stored in the sync database, keyed by extid with the item checksum.
+    type Diff: Processable + Guidable + Clone + Send + Sync;
+
+    fn to_update(&self) -> Self::Update;
+    fn to_diff(&self) -> Self::Diff;
+    /// Author or group the item was fetched for.
+    fn scope(&self) -> String;
+    /// Fields only kept in the sync database that differ from the `stored`
+    /// row, reported and recorded along with those of the main database.
+    fn changed_sync_fields(&self, _stored: Option<&Self::Diff>) -> Vec<FieldChange> {
+        Vec::new()
+    }
+}
+
+type Transform<C> = Box<dyn Fn(&mut C) + Send + Sync>;
+
+/// Diffs fetched items against the sync database by checksum and writes the
+/// new and changed ones to the main and sync databases, or plans them in a
+/// dry run. Optionally deletes the items a job stopped fetching and records
+/// the ones it failed to fetch as dead letters, and keeps a history of the
+/// fields it updates.
+pub struct SyncEngine<C, R: ?Sized, D: ?Sized> {
+    job: &'static str,
+    entity: &'static str,
+    repository: Arc<R>,
+    diff_repository: Arc<D>,
+    transforms: Vec<Transform<C>>,
+    delete_after_runs: i32,
+    dead_letter_repository: Option<Arc<dyn DeadLetterRepository>>,
+    field_change_repository: Option<Arc<dyn FieldChangeRepository>>,
+}
+
+impl<C, R, D> SyncEngine<C, R, D>
+where
+    C: SyncItem,
+    R: BatchOperations<C, C::Update, C::Current> + CurrentOperations<C::Current> + Send + Sync + ?Sized,
+    D: BatchOperations<C::Diff, C::Diff, C::Diff> + DiffOperations<C::Diff> + Send + Sync + ?Sized,
+{
+    /// Engine of `job`, writing `entity` items (named so in dry run reports,
+    /// traces and metrics).
+    pub fn new(job: &'static str, entity: &'static str, repository: Arc<R>, diff_repository: Arc<D>) -> Self {
+        Self {
+            job,
+            entity,
+            repository,
+            diff_repository,
+            transforms: Vec::new(),
+            delete_after_runs: 0,
+            dead_letter_repository: None,
+            field_change_repository: None,
+        }
+    }
+
+    /// Appends the fields every update changes, with their previous value, to
+    /// the `sync_field_change` table.
+    pub fn with_history(mut self, field_change_repository: Arc<dyn FieldChangeRepository>) -> Self {
+        self.field_change_repository = Some(field_change_repository);
+        self
+    }
+
+    /// Records fetch failures in the `sync_dead_letter` table instead of only
+    /// logging them.
+    pub fn with_dead_letters(mut self, dead_letter_repository: Arc<dyn DeadLetterRepository>) -> Self {
+        self.dead_letter_repository = Some(dead_letter_repository);
+        self
+    }
+
+    /// Deletes items missing from their source for `runs` complete runs in a
+    /// row; 0 keeps them forever.
+    pub fn with_delete_after_runs(mut self, runs: i32) -> Self {
+        self.delete_after_runs = runs;
+        self
+    }
+
+    /// Adds a transformation applied, in order, to every fetched item before
+    /// it is diffed and written.
+    pub fn with_transform(mut self, transform: impl Fn(&mut C) + Send + Sync + 'static) -> Self {
+        self.transforms.push(Box::new(transform));
+        self
+    }
+
+    /// Syncs a batch of items fetched from `source`.
+    pub async fn process(&self, mut items: Vec<C>, source: &str, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        for item in items.iter_mut() {
+            for transform in &self.transforms {
+                transform(item);
+            }
+        }
+
+        let total = items.len();
+        let extids: Vec<String> = items.iter().map(|item| item.get_extid()).collect();
+        let mut extids_by_scope: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for item in &items {
+            extids_by_scope.entry(item.scope()).or_default().push(item.get_extid());
+        }
+        let existing_items = self
+            .diff_repository
+            .find_by_extids(extids.clone())
+            .instrument(tracing::info_span!("find_by_extids", entity = self.entity, count = total))
+            .await?;
+
+        let mut items_to_insert: Vec<C> = Vec::new();
+        let mut items_to_update: Vec<C> = Vec::new();
+
+        for item in items {
+            match existing_items.iter().find(|existing_item| existing_item.get_extid() == item.get_extid()) {
+                Some(existing_item) if existing_item.get_checksum() != item.get_checksum() => {
+                    tracing::debug!(extid = %item.get_extid(), "Item changed");
+                    items_to_update.push(item);
+                }
+                Some(_) if context.is_forced() => {
+                    tracing::debug!(extid = %item.get_extid(), "Item forced");
+                    items_to_update.push(item);
+                }
+                Some(_) => {}
+                None => {
+                    tracing::debug!(extid = %item.get_extid(), "New item");
+                    items_to_insert.push(item);
+                }
+            }
+        }
+
+        let report = SyncReport {
+            inserted: items_to_insert.len() as i32,
+            updated: items_to_update.len() as i32,
+            unchanged: (total - items_to_insert.len() - items_to_update.len()) as i32,
+            deleted: 0,
+        };
+
+        if context.is_dry_run() {
+            self.plan_items(&items_to_insert, &items_to_update, &existing_items, context).await?;
+            return Ok(report);
+        }
+
+        self.insert_items(&items_to_insert).await?;
+        self.update_items(&items_to_update, &existing_items, context).await?;
+        if let Some(run_id) = context.run_id() {
+            for (scope, extids) in extids_by_scope {
+                let count = extids.len();
+                self.diff_repository
+                    .touch_many(extids, source, &scope, run_id)
+                    .instrument(tracing::info_span!("touch_many", entity = self.entity, count))
+                    .await?;
+            }
+        }
+        metrics::record_items(self.job, source, total, &report);
+
+        Ok(report)
+    }
+
+    /// Records the `failures` of a batch fetched from `source` for `scopes`,
+    /// and clears the dead letters of those scopes that fetched cleanly this
+    /// time. Dry runs only log them.
+    pub async fn dead_letter(
+        &self,
+        source: &str,
+        scopes: &[String],
+        failures: Vec<FetchFailure>,
+        context: &SyncContext,
+    ) -> Result<(), DomainError> {
+        for failure in &failures {
+            tracing::warn!(scope = %failure.scope, key = %failure.key, "Failed to fetch item: {}", failure.error);
+        }
+        metrics::record_failed(self.job, source, failures.len());
+
+        let dead_letter_repository = match &self.dead_letter_repository {
+            Some(dead_letter_repository) if !context.is_dry_run() => dead_letter_repository,
+            _ => return Ok(()),
+        };
+
+        let keys: Vec<String> = failures.iter().map(|failure| failure.key.clone()).collect();
+        for failure in failures {
+            dead_letter_repository
+                .upsert(&DeadLetterCreateModel::new(self.job, failure))
+                .await?;
+        }
+        if !scopes.is_empty() {
+            dead_letter_repository
+                .delete_resolved(self.job, source, scopes, &keys)
+                .instrument(tracing::info_span!("delete_resolved", entity = self.entity))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Ends a run: counts a missed run for every item of the scopes fetched
+    /// in full that the run did not fetch, within the window of the scopes
+    /// that have one, and deletes those missed for too many runs in a row.
+    /// Items of the other scopes are left alone. Dry runs and runs without a
+    /// `sync_run` row delete nothing.
+    pub async fn sweep(&self, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        let run_id = match context.run_id() {
+            Some(run_id) if self.delete_after_runs > 0 && !context.is_dry_run() => run_id,
+            _ => return Ok(SyncReport::default()),
+        };
+
+        let mut missing: Vec<String> = Vec::new();
+        for (source, scopes) in context.complete_scopes() {
+            let mut scopes_by_since: BTreeMap<Option<DateTime<Utc>>, Vec<String>> = BTreeMap::new();
+            for (scope, since) in scopes {
+                scopes_by_since.entry(since).or_default().push(scope);
+            }
+            for (since, scopes) in scopes_by_since {
+                missing.extend(
+                    self.diff_repository
+                        .mark_unseen(run_id, &source, scopes, since, self.delete_after_runs)
+                        .instrument(tracing::info_span!("mark_unseen", entity = self.entity, source = %source))
+                        .await?,
+                );
+            }
+        }
+
+        let mut deleted: Vec<String> = Vec::new();
+        let mut count = 0;
+        for extid in missing {
+            // A failed delete keeps the item missing, so the next run retries it
+            match self.repository.delete_current(&extid).await {
+                Ok(found) => {
+                    tracing::info!(extid = %extid, "Item missing from source, deleted");
+                    if found {
+                        count += 1;
+                    }
+                    deleted.push(extid);
+                }
+                Err(err) => tracing::error!(extid = %extid, "Failed to delete missing item: {}", err),
+            }
+        }
+
+        if !deleted.is_empty() {
+            self.diff_repository
+                .delete_by_extids(deleted)
+                .instrument(tracing::info_span!("delete_by_extids", entity = self.entity))
+                .await?;
+        }
+        metrics::record_deleted(self.job, count);
+
+        Ok(SyncReport {
+            deleted: count,
+            ..SyncReport::default()
+        })
+    }
+
+    async fn plan_items(&self, items_to_insert: &[C], items_to_update: &[C], stored: &[C::Diff], context: &SyncContext) -> Result<(), DomainError> {
+        for item in items_to_insert {
+            let mut fields = item.to_update().changed_fields(None);
+            fields.extend(item.changed_sync_fields(None));
+            context.plan(PlannedChange {
+                entity: self.entity.to_string(),
+                extid: item.get_extid(),
+                action: PlannedAction::Insert,
+                fields,
+            });
+        }
+
+        for item in items_to_update {
+            let current = self.repository.find_current(&item.get_extid()).await?;
+            let mut fields = item.to_update().changed_fields(current.as_ref());
+            fields.extend(item.changed_sync_fields(find_stored(stored, item)));
+            context.plan(PlannedChange {
+                entity: self.entity.to_string(),
+                extid: item.get_extid(),
+                action: PlannedAction::Update,
+                fields,
+            });
+        }
+        Ok(())
+    }
+
+    // The main database is written first and the sync database records the
+    // write. Both inserts are upserts, so when the second write fails the
+    // items are still new on the next run and are written again instead of
+    // hitting the extid constraint of the main database.
+    async fn insert_items(&self, items_to_insert: &[C]) -> Result<(), DomainError> {
+        if items_to_insert.is_empty() {
+            return Ok(());
+        }
+
+        let count = items_to_insert.len();
+        self.repository
+            .insert_many(items_to_insert.to_vec())
+            .instrument(tracing::info_span!("insert_many", entity = self.entity, db = "main", count))
+            .await?;
+        self.diff_repository
+            .insert_many(items_to_insert.iter().map(SyncItem::to_diff).collect())
+            .instrument(tracing::info_span!("insert_many", entity = self.entity, db = "sync", count))
+            .await?;
+        Ok(())
+    }
+
+    // The history is read from the main database before the update and
+    // recorded once the update is written.
+    async fn update_items(&self, items_to_update: &[C], stored: &[C::Diff], context: &SyncContext) -> Result<(), DomainError> {
+        if items_to_update.is_empty() {
+            return Ok(());
+        }
+
+        let changes = match &self.field_change_repository {
+            Some(_) => self.changes(items_to_update, stored, context).await?,
+            None => Vec::new(),
+        };
+
+        let count = items_to_update.len();
+        self.repository
+            .update_many(items_to_update.iter().map(SyncItem::to_update).collect())
+            .instrument(tracing::info_span!("update_many", entity = self.entity, db = "main", count))
+            .await?;
+        if let Some(field_change_repository) = &self.field_change_repository {
+            if !changes.is_empty() {
+                field_change_repository
+                    .insert_many(&changes)
+                    .instrument(tracing::info_span!("insert_many", entity = self.entity, db = "history", count = changes.len()))
+                    .await?;
+            }
+        }
+        self.diff_repository
+            .update_many(items_to_update.iter().map(SyncItem::to_diff).collect())
+            .instrument(tracing::info_span!("update_many", entity = self.entity, db = "sync", count))
+            .await?;
+        Ok(())
+    }
+
+    async fn changes(&self, items_to_update: &[C], stored: &[C::Diff], context: &SyncContext) -> Result<Vec<FieldChangeCreateModel>, DomainError> {
+        let mut changes = Vec::new();
+        for item in items_to_update {
+            let extid = item.get_extid();
+            let current = self.repository.find_current(&extid).await?;
+            changes.extend(
+                item.to_update()
+                    .changed_fields(current.as_ref())
+                    .into_iter()
+                    .chain(item.changed_sync_fields(find_stored(stored, item)))
+                    .map(|change| FieldChangeCreateModel::new(self.entity, &extid, change, context.run_id())),
+            );
+        }
+        Ok(changes)
+    }
+}
+
+// Row of the sync database stored for `item`
+fn find_stored<'a, C: SyncItem>(stored: &'a [C::Diff], item: &C) -> Option<&'a C::Diff> {
+    stored.iter().find(|stored| stored.get_extid() == item.get_extid())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::{
+        api::{lib::Force, shutdown::Shutdown},
+        domain::{
+            dead_letter::model::DeadLetterModel,
+            field_change::model::{FieldChange, FieldChangeModel, FieldChanges},
+        },
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct FakeItem {
+        extid: String,
+        name: String,
+        checksum: String,
+        scope: String,
+        tag: Option<String>,
+    }
+
+    impl FakeItem {
+        fn new(extid: &str, name: &str, checksum: &str) -> Self {
+            Self {
+                extid: extid.to_string(),
+                name: name.to_string(),
+                checksum: checksum.to_string(),
+                scope: "author".to_string(),
+                tag: None,
+            }
+        }
+
+        fn with_tag(mut self, tag: &str) -> Self {
+            self.tag = Some(tag.to_string());
+            self
+        }
+
+        fn in_scope(mut self, scope: &str) -> Self {
+            self.scope = scope.to_string();
+            self
+        }
+    }
+
+    impl Processable for FakeItem {
+        fn get_checksum(&self) -> String {
+            self.checksum.clone()
+        }
+    }
+
+    impl Guidable for FakeItem {
+        fn get_extid(&self) -> String {
+            self.extid.clone()
+        }
+
+        fn set_extid(&mut self, extid: String) {
+            self.extid = extid;
+        }
+    }
+
+    impl Diffable<FakeItem> for FakeItem {
+        fn changed_fields(&self, current: Option<&FakeItem>) -> Vec<FieldChange> {
+            let mut changes = FieldChanges::new();
+            changes.compare("name", current.map(|current| &current.name), &self.name);
+            changes.into_vec()
+        }
+    }
+
+    impl SyncItem for FakeItem {
+        type Update = FakeItem;
+        type Current = FakeItem;
+        type Diff = FakeItem;
+
+        fn to_update(&self) -> Self::Update {
+            self.clone()
+        }
+
+        fn to_diff(&self) -> Self::Diff {
+            self.clone()
+        }
+
+        fn scope(&self) -> String {
+            self.scope.clone()
+        }
+
+        fn changed_sync_fields(&self, stored: Option<&FakeItem>) -> Vec<FieldChange> {
+            let mut changes = FieldChanges::new();
+            changes.compare("tag", stored.map(|stored| &stored.tag), &self.tag);
+            changes.into_vec()
+        }
+    }
+
+    mock! {
+        pub FakeRepository { }
+
+        #[async_trait]
+        impl BatchOperations<FakeItem, FakeItem, FakeItem> for FakeRepository {
+            async fn insert_many(&self, items: Vec<FakeItem>) -> Result<Vec<FakeItem>, DomainError>;
+            async fn update_many(&self, items: Vec<FakeItem>) -> Result<Vec<FakeItem>, DomainError>;
+        }
+
+        #[async_trait]
+        impl CurrentOperations<FakeItem> for FakeRepository {
+            async fn find_current(&self, extid: &str) -> Result<Option<FakeItem>, DomainError>;
+            async fn delete_current(&self, extid: &str) -> Result<bool, DomainError>;
+        }
+
+        #[async_trait]
+        impl DiffOperations<FakeItem> for FakeRepository {
+            async fn find_by_extids(&self, extids: Vec<String>) -> Result<Vec<FakeItem>, DomainError>;
+            async fn touch_many(&self, extids: Vec<String>, source: &str, scope: &str, run_id: i32) -> Result<(), DomainError>;
+            async fn mark_unseen(&self, run_id: i32, source: &str, scopes: Vec<String>, since: Option<DateTime<Utc>>, missed_runs: i32) -> Result<Vec<String>, DomainError>;
+            async fn delete_by_extids(&self, extids: Vec<String>) -> Result<(), DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self, job: &Option<String>, page: &u32, page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<DeadLetterModel>, DomainError>;
+            async fn upsert(&self, dead_letter_create_model: &DeadLetterCreateModel) -> Result<DeadLetterModel, DomainError>;
+            async fn delete_resolved(&self, job: &str, source: &str, scopes: &[String], keys: &[String]) -> Result<(), DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeFieldChangeRepository { }
+
+        #[async_trait]
+        impl FieldChangeRepository for FakeFieldChangeRepository {
+            async fn find_by_extid(&self, entity: &str, extid: &str) -> Result<Vec<FieldChangeModel>, DomainError>;
+            async fn insert_many(&self, changes: &[FieldChangeCreateModel]) -> Result<(), DomainError>;
+        }
+    }
+
+    // Sync database holding `a` unchanged and `b` with an older checksum
+    fn diff_repository() -> MockFakeRepository {
+        let mut diff_repository = MockFakeRepository::new();
+        diff_repository
+            .expect_find_by_extids()
+            .returning(|_| Ok(vec![FakeItem::new("a", "", "1"), FakeItem::new("b", "", "1")]));
+        diff_repository
+    }
+
+    fn fetched() -> Vec<FakeItem> {
+        vec![
+            FakeItem::new("a", "unchanged", "1"),
+            FakeItem::new("b", "changed", "2"),
+            FakeItem::new("c", "new", "1"),
+        ]
+    }
+
+    fn context() -> SyncContext {
+        SyncContext::new(Shutdown::manual(Duration::ZERO).1)
+    }
+
+    #[tokio::test]
+    async fn it_should_write_new_and_changed_items() {
+        let mut repository = MockFakeRepository::new();
+        repository
+            .expect_insert_many()
+            .withf(|items| items.iter().map(|item| item.extid.as_str()).eq(["c"]))
+            .times(1)
+            .returning(Ok);
+        repository
+            .expect_update_many()
+            .withf(|items| items.iter().map(|item| item.extid.as_str()).eq(["b"]))
+            .times(1)
+            .returning(Ok);
+
+        let mut diff_repository = diff_repository();
+        diff_repository
+            .expect_insert_many()
+            .withf(|items| items == &[FakeItem::new("c", "new", "1")])
+            .times(1)
+            .returning(Ok);
+        diff_repository
+            .expect_update_many()
+            .withf(|items| items == &[FakeItem::new("b", "changed", "2")])
+            .times(1)
+            .returning(Ok);
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository));
+        let report = engine.process(fetched(), "source", &context()).await.unwrap();
+
+        assert_eq!(
+            report,
+            SyncReport {
+                inserted: 1,
+                updated: 1,
+                unchanged: 1,
+                deleted: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_record_history_of_updated_fields() {
+        let mut repository = MockFakeRepository::new();
+        repository.expect_insert_many().returning(Ok);
+        repository.expect_update_many().returning(Ok);
+        repository
+            .expect_find_current()
+            .withf(|extid| extid == "b")
+            .times(1)
+            .returning(|_| Ok(Some(FakeItem::new("b", "old", "1"))));
+
+        let mut diff_repository = diff_repository();
+        diff_repository.expect_insert_many().returning(Ok);
+        diff_repository.expect_update_many().returning(Ok);
+        diff_repository.expect_touch_many().returning(|_, _, _, _| Ok(()));
+
+        let mut field_change_repository = MockFakeFieldChangeRepository::new();
+        field_change_repository
+            .expect_insert_many()
+            .withf(|changes| {
+                changes
+                    == [FieldChangeCreateModel {
+                        entity: "fake".to_string(),
+                        extid: "b".to_string(),
+                        field: "name".to_string(),
+                        before: Some("old".to_string()),
+                        after: Some("changed".to_string()),
+                        run_id: Some(7),
+                    }]
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository))
+            .with_history(Arc::new(field_change_repository));
+        let context = context().with_run_id(Some(7));
+
+        assert!(engine.process(fetched(), "source", &context).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_record_history_of_fields_kept_in_sync_database() {
+        let mut repository = MockFakeRepository::new();
+        repository.expect_update_many().returning(Ok);
+        repository
+            .expect_find_current()
+            .returning(|_| Ok(Some(FakeItem::new("a", "unchanged", "1"))));
+
+        let mut diff_repository = MockFakeRepository::new();
+        diff_repository
+            .expect_find_by_extids()
+            .returning(|_| Ok(vec![FakeItem::new("a", "", "1").with_tag("rust")]));
+        diff_repository.expect_update_many().returning(Ok);
+
+        let mut field_change_repository = MockFakeFieldChangeRepository::new();
+        field_change_repository
+            .expect_insert_many()
+            .withf(|changes| {
+                changes
+                    == [FieldChangeCreateModel {
+                        entity: "fake".to_string(),
+                        extid: "a".to_string(),
+                        field: "tag".to_string(),
+                        before: Some("rust".to_string()),
+                        after: Some("postgres".to_string()),
+                        run_id: None,
+                    }]
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository))
+            .with_history(Arc::new(field_change_repository));
+        let fetched = vec![FakeItem::new("a", "unchanged", "2").with_tag("postgres")];
+
+        assert!(engine.process(fetched, "source", &context()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_rewrite_unchanged_items_forced() {
+        let mut repository = MockFakeRepository::new();
+        repository.expect_insert_many().returning(Ok);
+        repository
+            .expect_update_many()
+            .withf(|items| items.iter().map(|item| item.extid.as_str()).eq(["a", "b"]))
+            .times(1)
+            .returning(Ok);
+
+        let mut diff_repository = diff_repository();
+        diff_repository.expect_insert_many().returning(Ok);
+        diff_repository
+            .expect_update_many()
+            .withf(|items| items.iter().map(|item| item.extid.as_str()).eq(["a", "b"]))
+            .times(1)
+            .returning(Ok);
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository));
+        let context = context().with_force(Some(Force::default()));
+        let report = engine.process(fetched(), "source", &context).await.unwrap();
+
+        assert_eq!(report.updated, 2);
+        assert_eq!(report.unchanged, 0);
+    }
+
+    #[tokio::test]
+    async fn it_should_plan_without_writing_dry_run() {
+        let mut repository = MockFakeRepository::new();
+        repository
+            .expect_find_current()
+            .withf(|extid| extid == "b")
+            .returning(|_| Ok(Some(FakeItem::new("b", "stored", "1"))));
+        repository.expect_insert_many().never();
+        repository.expect_update_many().never();
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository()));
+        let context = SyncContext::dry_run(Shutdown::manual(Duration::ZERO).1);
+        engine.process(fetched(), "source", &context).await.unwrap();
+
+        let report = context.into_report();
+        assert_eq!(report.changes.len(), 2);
+        assert_eq!(report.changes[0].extid, "c");
+        assert_eq!(report.changes[0].action, PlannedAction::Insert);
+        assert_eq!(
+            report.changes[1].fields,
+            vec![FieldChange {
+                field: "name".to_string(),
+                before: Some("stored".to_string()),
+                after: Some("changed".to_string()),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn it_should_transform_items_before_diffing() {
+        let mut repository = MockFakeRepository::new();
+        repository
+            .expect_insert_many()
+            .withf(|items| items.iter().all(|item| item.name == item.name.to_uppercase()))
+            .returning(Ok);
+        repository
+            .expect_update_many()
+            .withf(|items| items.iter().all(|item| item.name == item.name.to_uppercase()))
+            .returning(Ok);
+
+        let mut diff_repository = diff_repository();
+        diff_repository.expect_insert_many().returning(Ok);
+        diff_repository.expect_update_many().returning(Ok);
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository))
+            .with_transform(|item: &mut FakeItem| item.name = item.name.to_uppercase())
+            // A checksum bump turns the unchanged item into an update
+            .with_transform(|item: &mut FakeItem| item.checksum = format!("{}-v2", item.checksum));
+        let report = engine.process(fetched(), "source", &context()).await.unwrap();
+
+        assert_eq!(report.updated, 2);
+        assert_eq!(report.unchanged, 0);
+    }
+
+    #[tokio::test]
+    async fn it_should_mark_fetched_items_seen_by_run() {
+        let mut repository = MockFakeRepository::new();
+        repository.expect_insert_many().returning(Ok);
+        repository.expect_update_many().returning(Ok);
+
+        let mut diff_repository = diff_repository();
+        diff_repository.expect_insert_many().returning(Ok);
+        diff_repository.expect_update_many().returning(Ok);
+        diff_repository
+            .expect_touch_many()
+            .withf(|extids, source, scope, run_id| extids == &["a", "b"] && source == "source" && scope == "author" && *run_id == 7)
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+        diff_repository
+            .expect_touch_many()
+            .withf(|extids, _, scope, _| extids == &["c"] && scope == "other")
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let mut fetched = fetched();
+        fetched[2] = fetched[2].clone().in_scope("other");
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository));
+        engine
+            .process(fetched, "source", &context().with_run_id(Some(7)))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn it_should_delete_items_missing_for_too_many_runs() {
+        let mut repository = MockFakeRepository::new();
+        repository
+            .expect_delete_current()
+            .withf(|extid| extid == "x")
+            .returning(|_| Ok(true));
+        repository
+            .expect_delete_current()
+            .withf(|extid| extid == "y")
+            .returning(|_| Err(DomainError::InternalServerError("still referenced".to_string())));
+
+        let mut diff_repository = MockFakeRepository::new();
+        diff_repository
+            .expect_mark_unseen()
+            .withf(|run_id, source, scopes, since, missed_runs| {
+                *run_id == 7 && source == "source" && scopes == &["author", "other"] && since.is_none() && *missed_runs == 3
+            })
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(vec!["x".to_string(), "y".to_string()]));
+        // The item that failed to delete stays in the sync database and is retried
+        diff_repository
+            .expect_delete_by_extids()
+            .withf(|extids| extids == &["x"])
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository))
+            .with_delete_after_runs(3);
+        let context = context().with_run_id(Some(7));
+        context.complete_scope("source", "author");
+        context.complete_scope("source", "other");
+        let report = engine.sweep(&context).await.unwrap();
+
+        assert_eq!(report.deleted, 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_only_count_missing_items_within_window() {
+        let since = DateTime::parse_from_rfc3339("2023-10-10T13:00:00Z").unwrap().with_timezone(&Utc);
+
+        let mut diff_repository = MockFakeRepository::new();
+        diff_repository
+            .expect_mark_unseen()
+            .withf(|_, _, scopes, since, _| scopes == &["author"] && since.is_none())
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(Vec::new()));
+        diff_repository
+            .expect_mark_unseen()
+            .withf(move |_, _, scopes, window, _| scopes == &["feed"] && *window == Some(since))
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(Vec::new()));
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(MockFakeRepository::new()), Arc::new(diff_repository))
+            .with_delete_after_runs(3);
+        let context = context().with_run_id(Some(7));
+        context.complete_scope("source", "author");
+        context.complete_window("source", "feed", since);
+
+        assert_eq!(engine.sweep(&context).await.unwrap(), SyncReport::default());
+    }
+
+    #[tokio::test]
+    async fn it_should_not_delete_scopes_not_fetched_in_full() {
+        let mut diff_repository = MockFakeRepository::new();
+        diff_repository.expect_mark_unseen().never();
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(MockFakeRepository::new()), Arc::new(diff_repository))
+            .with_delete_after_runs(3);
+        let report = engine.sweep(&context().with_run_id(Some(7))).await.unwrap();
+
+        assert_eq!(report, SyncReport::default());
+    }
+
+    #[tokio::test]
+    async fn it_should_not_delete_dry_run() {
+        let mut diff_repository = MockFakeRepository::new();
+        diff_repository.expect_mark_unseen().never();
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(MockFakeRepository::new()), Arc::new(diff_repository))
+            .with_delete_after_runs(3);
+        let context = SyncContext::dry_run(Shutdown::manual(Duration::ZERO).1).with_run_id(Some(7));
+        let report = engine.sweep(&context).await.unwrap();
+
+        assert_eq!(report, SyncReport::default());
+    }
+
+    #[tokio::test]
+    async fn it_should_write_again_items_not_recorded_in_sync_database() {
+        let mut repository = MockFakeRepository::new();
+        repository
+            .expect_insert_many()
+            .withf(|items| items.iter().map(|item| item.extid.as_str()).eq(["c"]))
+            .times(2)
+            .returning(Ok);
+        repository.expect_update_many().returning(Ok);
+
+        let mut diff_repository = diff_repository();
+        diff_repository
+            .expect_insert_many()
+            .times(1)
+            .returning(|_| Err(DomainError::InternalServerError("connection lost".to_string())));
+        diff_repository.expect_insert_many().times(1).returning(Ok);
+        diff_repository.expect_update_many().returning(Ok);
+
+        let engine = SyncEngine::new("fakes", "fake", Arc::new(repository), Arc::new(diff_repository));
+
+        assert!(engine.process(fetched(), "source", &context()).await.is_err());
+        let report = engine.process(fetched(), "source", &context()).await.unwrap();
+
+        assert_eq!(report.inserted, 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_record_failures_and_clear_resolved_dead_letters() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_upsert()
+            .withf(|model| model.job == "fakes" && model.scope == "kozyrkov" && model.key == "b")
+            .times(1)
+            .returning(|_| Ok(DeadLetterModel::mock_default()));
+        dead_letter_repository
+            .expect_delete_resolved()
+            .withf(|job, source, scopes, keys| {
+                job == "fakes" && source == "medium" && scopes == ["kozyrkov", "tdavidson"] && keys == ["b"]
+            })
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let engine = SyncEngine::<FakeItem, MockFakeRepository, MockFakeRepository>::new(
+            "fakes",
+            "fake",
+            Arc::new(MockFakeRepository::new()),
+            Arc::new(MockFakeRepository::new()),
+        )
+        .with_dead_letters(Arc::new(dead_letter_repository));
+        let failures = vec![FetchFailure::new("medium", "kozyrkov", "b", "Missing image")];
+        let scopes = vec!["kozyrkov".to_string(), "tdavidson".to_string()];
+
+        assert!(engine.dead_letter("medium", &scopes, failures, &context()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_not_record_failures_dry_run() {
+        let engine = SyncEngine::<FakeItem, MockFakeRepository, MockFakeRepository>::new(
+            "fakes",
+            "fake",
+            Arc::new(MockFakeRepository::new()),
+            Arc::new(MockFakeRepository::new()),
+        )
+        .with_dead_letters(Arc::new(MockFakeDeadLetterRepository::new()));
+        let failures = vec![FetchFailure::scope("medium", "kozyrkov", "Invalid feed")];
+        let context = SyncContext::dry_run(Shutdown::manual(Duration::ZERO).1);
+
+        assert!(engine.dead_letter("medium", &["kozyrkov".to_string()], failures, &context).await.is_ok());
+    }
+}
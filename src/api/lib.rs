@@ -1,19 +1,20 @@
 use async_trait::async_trait;
-use chrono::{Local, Timelike};
+use chrono::{DateTime, Utc};
 use deadpool_postgres::Pool;
-use tokio::{time::{interval_at, Duration, Instant}, sync::Mutex};
-use std::sync::Arc;
-use std::error::Error;
+use tokio::{sync::Mutex, task::JoinSet};
+use std::{collections::BTreeMap, sync::{Arc, Mutex as StdMutex}};
+use tracing::Instrument;
 
-use crate::{domain::{error::DomainError, article::model::{Processable, Guidable}}, infrastructure::{repository::{
-    postgres::{group::PgGroupRepository, article::PgArticleRepository, city::PgCityRepository, event::PgEventRepository}, sync::{postgres::{run_migrations as sync_migrations},diff_article::PgDiffArticleRepository, registered_author::PgRegisteredAuthorRepository, diff_group::PgDiffGroupRepository, registered_group::PgRegisteredGroupRepository, diff_event::PgDiffEventRepository}}, adapter::{meetup_group::{RateLimitedClient, MeetupGroupAdapter}, medium_article::{ MediumArticleAdapter}, meetup_event::MeetupEventAdapter}}};
+use crate::{domain::{article::adapter::ArticleAdapter, group::adapter::GroupAdapter, error::DomainError, article::model::{Processable, Guidable}, sync_run::model::SyncReport}, infrastructure::{repository::{
+    postgres::{health::PgHealthRepository, group::PgGroupRepository, article::PgArticleRepository, city::PgCityRepository, event::PgEventRepository}, sync::{diff_article::PgDiffArticleRepository, registered_author::PgRegisteredAuthorRepository, diff_group::PgDiffGroupRepository, registered_group::PgRegisteredGroupRepository, diff_event::PgDiffEventRepository, sync_run::PgSyncRunRepository, sync_lock::PgSyncLockRepository, dead_letter::PgDeadLetterRepository, field_change::PgFieldChangeRepository}}, adapter::{meetup_group::{RateLimitedClient, MeetupGroupAdapter}, feed_article::{FeedArticleAdapter, MEDIUM_FEED_URL}, substack_article::SubstackArticleAdapter, forem_article::{ForemArticleAdapter, DEVTO_API_URL}, hashnode_article::HashnodeArticleAdapter, meetup_event::MeetupEventAdapter}, metrics}};
 
 // The services
-use super::services::{article_sync, group_sync, event_sync};
+use super::{admin::{self, AdminState}, config::get_config, registry::AdapterRegistry, services::{article_sync, group_sync, event_sync}, dry_run::{DryRunReport, PlannedChange}, shutdown::Shutdown, supervisor::Supervisor};
 
 pub struct Scheduler {
     pg_pool: Arc<Pool>,
     sync_pool: Arc<Pool>,
+    shutdown: Shutdown,
 }
 
 #[async_trait]
@@ -25,48 +26,297 @@ pub trait BatchOperations<T,D,G> {
 #[async_trait]
 pub trait DiffOperations<T: Processable + Guidable> {
     async fn find_by_extids(&self, extids: Vec<String>) -> Result<Vec<T>, DomainError>;
+    /// Marks `extids` as fetched by `run_id` for `scope`, an author or a group
+    /// of `source`.
+    async fn touch_many(&self, extids: Vec<String>, source: &str, scope: &str, run_id: i32) -> Result<(), DomainError>;
+    /// Counts a missed run for every item of `scopes` that `run_id` did not
+    /// fetch, only those dated from `since` on when set, and returns the
+    /// extids of those missed for `missed_runs` runs in a row.
+    async fn mark_unseen(&self, run_id: i32, source: &str, scopes: Vec<String>, since: Option<DateTime<Utc>>, missed_runs: i32) -> Result<Vec<String>, DomainError>;
+    async fn delete_by_extids(&self, extids: Vec<String>) -> Result<(), DomainError>;
 }
 
+#[async_trait]
+pub trait CurrentOperations<G> {
+    /// Row currently stored for `extid`, to report what a write would change.
+    async fn find_current(&self, extid: &str) -> Result<Option<G>, DomainError>;
+    /// Deletes the row stored for `extid`, returning whether there was one.
+    async fn delete_current(&self, extid: &str) -> Result<bool, DomainError>;
+}
+
+#[async_trait]
+pub trait SyncJob: Send + Sync {
+    /// Name used for the job's schedule (`SYNC_SCHEDULE_<NAME>`) and on the command line.
+    fn name(&self) -> &'static str;
+    /// Syncs every source, stopping between batches once shutdown is requested.
+    async fn sync(&self, context: &SyncContext) -> Result<SyncReport, DomainError>;
+}
+
+/// Run rewriting the fetched items whatever their checksum, optionally only
+/// those of a source, an author or a group (by slug).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Force {
+    pub source: Option<String>,
+    pub author: Option<String>,
+    pub group: Option<String>,
+}
+
+impl Force {
+    pub fn includes_author(&self, source: &str, author: &str) -> bool {
+        self.group.is_none() && matches(&self.source, source) && matches(&self.author, author)
+    }
+
+    pub fn includes_group(&self, source: &str, group: &str) -> bool {
+        self.author.is_none() && matches(&self.source, source) && matches(&self.group, group)
+    }
+}
+
+fn matches(filter: &Option<String>, value: &str) -> bool {
+    filter.as_deref().is_none_or(|filter| filter == value)
+}
+
+/// Scopes fetched in full, with the start of their window, by source.
+pub type CompleteScopes = BTreeMap<String, Vec<(String, Option<DateTime<Utc>>)>>;
+
+/// A single run of a job: whether to stop and whether to write at all.
+pub struct SyncContext {
+    shutdown: Shutdown,
+    dry_run: Option<StdMutex<DryRunReport>>,
+    run_id: Option<i32>,
+    force: Option<Force>,
+    complete_scopes: StdMutex<CompleteScopes>,
+}
+
+impl SyncContext {
+    pub fn new(shutdown: Shutdown) -> Self {
+        Self {
+            shutdown,
+            dry_run: None,
+            run_id: None,
+            force: None,
+            complete_scopes: StdMutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Ties the run to its `sync_run` row, which marks the items it fetched.
+    pub fn with_run_id(mut self, run_id: Option<i32>) -> Self {
+        self.run_id = run_id;
+        self
+    }
+
+    /// Run that plans its writes in a report instead of making them.
+    pub fn dry_run(shutdown: Shutdown) -> Self {
+        Self {
+            shutdown,
+            dry_run: Some(StdMutex::new(DryRunReport::default())),
+            run_id: None,
+            force: None,
+            complete_scopes: StdMutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn is_shutdown_requested(&self) -> bool {
+        self.shutdown.is_requested()
+    }
+
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run.is_some()
+    }
+
+    pub fn with_force(mut self, force: Option<Force>) -> Self {
+        self.force = force;
+        self
+    }
+
+    pub fn run_id(&self) -> Option<i32> {
+        self.run_id
+    }
+
+    pub fn is_forced(&self) -> bool {
+        self.force.is_some()
+    }
+
+    pub fn includes_author(&self, source: &str, author: &str) -> bool {
+        self.force.as_ref().is_none_or(|force| force.includes_author(source, author))
+    }
+
+    pub fn includes_group(&self, source: &str, group: &str) -> bool {
+        self.force.as_ref().is_none_or(|force| force.includes_group(source, group))
+    }
+
+    /// Records that the run fetched every item of `scope`, an author or a
+    /// group of `source`, so the items of `scope` it did not fetch are gone.
+    pub fn complete_scope(&self, source: &str, scope: &str) {
+        self.complete(source, scope, None);
+    }
+
+    /// Records that the run fetched every item of `scope` dated from `since`
+    /// on, all a source listing a window (latest articles, upcoming events)
+    /// tells, so only the items of that window it did not fetch are gone.
+    pub fn complete_window(&self, source: &str, scope: &str, since: DateTime<Utc>) {
+        self.complete(source, scope, Some(since));
+    }
+
+    fn complete(&self, source: &str, scope: &str, since: Option<DateTime<Utc>>) {
+        self.complete_scopes
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .entry(source.to_string())
+            .or_default()
+            .push((scope.to_string(), since));
+    }
+
+    /// Scopes fetched in full by the run, with the start of their window, by
+    /// source.
+    pub fn complete_scopes(&self) -> CompleteScopes {
+        self.complete_scopes.lock().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// Records a write the dry run would have made.
+    pub fn plan(&self, change: PlannedChange) {
+        if let Some(report) = &self.dry_run {
+            report.lock().unwrap_or_else(|err| err.into_inner()).push(change);
+        }
+    }
+
+    pub fn into_report(self) -> DryRunReport {
+        self.dry_run
+            .map(|report| report.into_inner().unwrap_or_else(|err| err.into_inner()))
+            .unwrap_or_default()
+    }
+}
 
 impl Scheduler {
     pub fn new(pg_pool: Arc<Pool>, sync_pool: Arc<Pool>) -> Scheduler {
-        Scheduler { pg_pool, sync_pool }
+        let shutdown = Shutdown::listen(get_config().shutdown_timeout);
+
+        Scheduler { pg_pool, sync_pool, shutdown }
     }
 
-    pub async fn run(&self) -> Result<(), Box<dyn Error>> {
-        sync_migrations().await?;
-        // Define the times of day to run each task
-        let article_hour = 1; // Run at 1 AM
-        let group_hour = 2; // Run at 2 AM
-        let event_hour = 3; // Run at 3 AM
+    /// Runs every job until shutdown, each one on its own schedule and
+    /// independently of the others' failures, along with the admin server.
+    pub async fn run(&self) -> Result<(), DomainError> {
+        let config = get_config();
+        let web_addr = config.web_addr()?;
+        let supervisor = self.supervisor();
+        let jobs = self.jobs();
 
-        // Calculate the duration until the next occurrence of each time
-        let article_duration_until = self.duration_until_hour(article_hour);
-        let group_duration_until = self.duration_until_hour(group_hour);
-        let event_duration_until = self.duration_until_hour(event_hour);
+        let mut tasks = JoinSet::new();
+        for job in jobs.iter().cloned() {
+            let schedule = config.schedule(job.name(), default_schedule(job.name()))?;
+            let supervisor = supervisor.clone();
+            tasks.spawn(async move { supervisor.supervise(job, schedule).await });
+        }
 
-        // Calculate the Instant for each start time
-        let start_instant = Instant::now();
-        let article_start_time = start_instant + article_duration_until;
-        let group_start_time = start_instant + group_duration_until;
-        let event_start_time = start_instant + event_duration_until;
+        metrics::register_pools(vec![
+            ("database", self.pg_pool.as_ref().clone()),
+            ("sync_database", self.sync_pool.as_ref().clone()),
+        ])?;
+
+        let admin_state = AdminState::new(
+            vec![
+                ("database", Arc::new(PgHealthRepository::new(self.pg_pool.clone()))),
+                ("sync_database", Arc::new(PgHealthRepository::new(self.sync_pool.clone()))),
+            ],
+            Arc::new(PgSyncRunRepository::new(self.sync_pool.clone())),
+            jobs,
+            supervisor,
+        );
+        let shutdown = self.shutdown.clone();
+        tasks.spawn(async move {
+            if let Err(err) = admin::serve(web_addr, admin_state, shutdown).await {
+                tracing::error!("{}", err);
+            }
+        });
+
+        while tasks.join_next().await.is_some() {}
+
+        Ok(())
+    }
+
+    /// Runs a single pass of the named jobs, in dependency order, and reports
+    /// the ones that failed. A pass cut short by shutdown fails too, its
+    /// remaining jobs never having run.
+    pub async fn run_once(&self, names: &[&str], force: Option<Force>) -> Result<(), DomainError> {
+        let mut failed: Vec<&str> = Vec::new();
+
+        let supervisor = self.supervisor().with_force(force);
+
+        for job in self.jobs().into_iter().filter(|job| names.contains(&job.name())) {
+            match supervisor.run_with_retries(job.clone()).await {
+                Ok(None) => {}
+                Ok(Some(report)) => tracing::info!(
+                    "{} sync done: {} inserted, {} updated, {} unchanged, {} deleted",
+                    job.name(),
+                    report.inserted,
+                    report.updated,
+                    report.unchanged,
+                    report.deleted
+                ),
+                Err(err) => {
+                    tracing::error!("{} sync failed: {}", job.name(), err);
+                    failed.push(job.name());
+                }
+            }
+        }
+
+        if !failed.is_empty() {
+            return Err(DomainError::InternalServerError(format!("Sync failed for: {}", failed.join(", "))));
+        }
+
+        if self.shutdown.is_requested() {
+            return Err(DomainError::InternalServerError("Sync interrupted by shutdown".to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn supervisor(&self) -> Supervisor {
+        let config = get_config();
+
+        Supervisor::new(
+            Arc::new(PgSyncRunRepository::new(self.sync_pool.clone())),
+            Arc::new(PgSyncLockRepository::new(self.sync_pool.clone())),
+            config.retry_policy.clone(),
+            config.catch_up_grace,
+            config.lock_mode,
+            self.shutdown.clone(),
+        )
+    }
 
-        // Set up the intervals to run every 24 hours
-        let article_interval = interval_at(article_start_time, Duration::from_secs(24 * 3600));
-        let group_interval = interval_at(group_start_time, Duration::from_secs(24 * 3600));
-        let event_interval = interval_at(event_start_time, Duration::from_secs(24 * 3600));
+    /// Runs the named jobs without writing, reporting what they would change.
+    pub async fn dry_run(&self, names: &[&str], force: Option<Force>) -> Result<DryRunReport, DomainError> {
+        let mut report = DryRunReport::default();
 
+        for job in self.jobs().into_iter().filter(|job| names.contains(&job.name())) {
+            let context = SyncContext::dry_run(self.shutdown.clone()).with_force(force.clone());
+            job.sync(&context)
+                .instrument(tracing::info_span!("job", job = job.name(), dry_run = true))
+                .await?;
+            report.extend(context.into_report());
+        }
+
+        Ok(report)
+    }
+
+    /// Every sync job, events last since they are fetched for the synced groups.
+    fn jobs(&self) -> Vec<Arc<dyn SyncJob>> {
+        let config = get_config();
         let article_repository = Arc::new(PgArticleRepository::new(self.pg_pool.clone()));
         let diff_article_repository = Arc::new(PgDiffArticleRepository::new(self.sync_pool.clone()));
         let registered_author_repository = Arc::new(PgRegisteredAuthorRepository::new(self.sync_pool.clone()));
+        let dead_letter_repository = Arc::new(PgDeadLetterRepository::new(self.sync_pool.clone()));
+        let field_change_repository = Arc::new(PgFieldChangeRepository::new(self.sync_pool.clone()));
 
         // The tasks
-        let mut article_sync_task = article_sync::ArticleSync::new(
-            MediumArticleAdapter::new(),
-            article_repository.clone(), 
-            diff_article_repository.clone(), 
+        let article_sync_task = article_sync::ArticleSync::new(
+            article_adapters(),
+            article_repository.clone(),
+            diff_article_repository.clone(),
             registered_author_repository.clone(),
-            article_interval
+            dead_letter_repository.clone(),
+            field_change_repository.clone(),
+            config.delete_after_runs("articles"),
         );
 
         let rate_limited_client = Arc::new(Mutex::new(RateLimitedClient::new()));
@@ -79,12 +329,14 @@ impl Scheduler {
 
         let rate_limited_client_clone = rate_limited_client.clone();
 
-        let mut group_sync_task = group_sync::GroupSync::new(
-            MeetupGroupAdapter::new(rate_limited_client_clone,city_repository.clone()),
-            group_repository.clone(), 
-            diff_group_repository.clone(), 
+        let group_sync_task = group_sync::GroupSync::new(
+            group_adapters(rate_limited_client_clone, city_repository.clone()),
+            group_repository.clone(),
+            diff_group_repository.clone(),
             registered_group_repository.clone(),
-            group_interval
+            dead_letter_repository.clone(),
+            field_change_repository.clone(),
+            config.delete_after_runs("groups"),
         );
 
         let event_repository = Arc::new(PgEventRepository::new(self.pg_pool.clone()));
@@ -92,42 +344,82 @@ impl Scheduler {
 
         let rate_limited_client_clone = rate_limited_client.clone();
 
-        let mut event_sync_task = event_sync::EventSync::new(
+        let event_sync_task = event_sync::EventSync::new(
             MeetupEventAdapter::new(rate_limited_client_clone),
-            event_repository.clone(), 
-            diff_event_repository.clone(), 
-            group_repository.clone(), 
-            event_interval,
+            event_repository.clone(),
+            diff_event_repository.clone(),
+            group_repository.clone(),
+            dead_letter_repository.clone(),
+            field_change_repository.clone(),
+            config.delete_after_runs("events"),
         );
 
-       
+        vec![
+            Arc::new(article_sync_task),
+            Arc::new(group_sync_task),
+            Arc::new(event_sync_task),
+        ]
+    }
+}
 
-        tokio::try_join!(
-            article_sync_task.start(),
-            group_sync_task.start(),
-            event_sync_task.start()
-        )?;
+/// Sources an author can be registered with, those of the article adapters.
+pub fn article_sources() -> Vec<String> {
+    article_adapters().sources().into_iter().map(String::from).collect()
+}
 
-        Ok(())
+/// Sources a group can be registered with, those of the group adapters
+/// (built on `pg_pool` without connecting).
+pub fn group_sources(pg_pool: Arc<Pool>) -> Vec<String> {
+    let city_repository = Arc::new(PgCityRepository::new(pg_pool));
+
+    group_adapters(Arc::new(Mutex::new(RateLimitedClient::new())), city_repository)
+        .sources()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Article adapters by the source of the registered authors.
+fn article_adapters() -> AdapterRegistry<dyn ArticleAdapter> {
+    AdapterRegistry::<dyn ArticleAdapter>::new("article")
+        .register("medium", Arc::new(FeedArticleAdapter::new().with_default_feed_url(MEDIUM_FEED_URL)))
+        .register("feed", Arc::new(FeedArticleAdapter::new()))
+        .register("substack", Arc::new(SubstackArticleAdapter::new()))
+        .register("devto", Arc::new(ForemArticleAdapter::new(DEVTO_API_URL)))
+        .register("hashnode", Arc::new(HashnodeArticleAdapter::new()))
+}
+
+/// Group adapters by the source of the registered groups.
+fn group_adapters(client: Arc<Mutex<RateLimitedClient>>, city_repository: Arc<PgCityRepository>) -> AdapterRegistry<dyn GroupAdapter> {
+    AdapterRegistry::<dyn GroupAdapter>::new("group")
+        .register("meetup", Arc::new(MeetupGroupAdapter::new(client, city_repository)))
+}
+
+fn default_schedule(job: &str) -> &'static str {
+    match job {
+        "articles" => "0 0 1 * * *", // Run at 1 AM
+        "groups" => "0 0 2 * * *", // Run at 2 AM
+        "events" => "0 0 3 * * *", // Run at 3 AM
+        _ => "@daily",
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use deadpool_postgres::Manager;
+    use tokio_postgres::NoTls;
+
+    use super::*;
 
-    fn duration_until_hour(&self, hour: u32) -> Duration {
-        let now = Local::now();
-        let next = now
-            .with_hour(hour).unwrap()
-            .with_minute(0).unwrap()
-            .with_second(0).unwrap()
-            .with_nanosecond(0).unwrap();
-    
-        let next = if next > now {
-            next
-        } else {
-            next + chrono::Duration::days(1)
-        };
-    
-        let duration_until_next = next.signed_duration_since(now);
-        let duration_secs = duration_until_next.num_seconds() as u64;
-        Duration::from_secs(duration_secs)
-        
-    }
-}
\ No newline at end of file
+    #[test]
+    fn it_should_list_article_sources_of_the_adapters() {
+        assert_eq!(article_sources(), vec!["devto", "feed", "hashnode", "medium", "substack"]);
+    }
+
+    #[test]
+    fn it_should_list_group_sources_of_the_adapters() {
+        let pool = Pool::builder(Manager::new(tokio_postgres::Config::new(), NoTls)).build().unwrap();
+
+        assert_eq!(group_sources(Arc::new(pool)), vec!["meetup"]);
+    }
+}
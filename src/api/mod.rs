@@ -1,6 +1,15 @@
+pub mod cli;
+pub mod commands;
+pub mod dry_run;
 pub mod lib;
 
+mod admin;
 mod config;
+mod engine;
+mod registry;
+mod schedule;
 mod services;
+mod shutdown;
+mod supervisor;
 
-pub mod utils;
\ No newline at end of file
+pub mod utils;
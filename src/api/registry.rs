@@ -0,0 +1,83 @@
+use std::{collections::HashMap, sync::Arc};
+
+use crate::domain::error::DomainError;
+
+/// Adapters of an entity keyed by the `source` of the registered authors or
+/// groups they fetch.
+pub struct AdapterRegistry<A: ?Sized> {
+    entity: &'static str,
+    adapters: HashMap<String, Arc<A>>,
+}
+
+impl<A: ?Sized> AdapterRegistry<A> {
+    /// Registry of the adapters fetching `entity` items (named so in errors).
+    pub fn new(entity: &'static str) -> Self {
+        Self {
+            entity,
+            adapters: HashMap::new(),
+        }
+    }
+
+    /// Routes the authors or groups of `source` to `adapter`.
+    pub fn register(mut self, source: &str, adapter: Arc<A>) -> Self {
+        self.adapters.insert(source.to_string(), adapter);
+        self
+    }
+
+    /// Adapter of `source`, a configuration error when none is registered.
+    pub fn get(&self, source: &str) -> Result<&Arc<A>, DomainError> {
+        self.adapters.get(source).ok_or_else(|| {
+            DomainError::BadRequest(format!(
+                "No {} adapter registered for source '{}', expected one of {}",
+                self.entity,
+                source,
+                self.sources().join(", ")
+            ))
+        })
+    }
+
+    /// Sources with an adapter, sorted.
+    pub fn sources(&self) -> Vec<&str> {
+        let mut sources: Vec<&str> = self.adapters.keys().map(String::as_str).collect();
+        sources.sort_unstable();
+        sources
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    trait FakeAdapter: Send + Sync {
+        fn name(&self) -> &'static str;
+    }
+
+    struct Medium;
+
+    impl FakeAdapter for Medium {
+        fn name(&self) -> &'static str {
+            "medium"
+        }
+    }
+
+    #[test]
+    fn it_should_route_source_to_its_adapter() {
+        let registry = AdapterRegistry::<dyn FakeAdapter>::new("article").register("medium", Arc::new(Medium));
+
+        assert_eq!(registry.get("medium").unwrap().name(), "medium");
+    }
+
+    #[test]
+    fn it_should_return_error_unknown_source() {
+        let registry = AdapterRegistry::<dyn FakeAdapter>::new("article")
+            .register("medium", Arc::new(Medium))
+            .register("feed", Arc::new(Medium));
+
+        match registry.get("tumblr") {
+            Err(DomainError::BadRequest(message)) => {
+                assert_eq!(message, "No article adapter registered for source 'tumblr', expected one of feed, medium")
+            }
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+use crate::domain::error::DomainError;
+
+// Longest daylight saving gap we try to step over (a few zones shift by more than an hour)
+const MAX_GAP_MINUTES: i64 = 3 * 60;
+
+#[derive(Debug, Clone)]
+enum ScheduleKind {
+    Cron(Box<cron::Schedule>),
+    Interval(Duration),
+}
+
+/// When a sync job should fire: either a cron expression evaluated on the
+/// wall clock of `timezone`, or a fixed interval (`@every 2h`).
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    expression: String,
+    kind: ScheduleKind,
+    timezone: Tz,
+}
+
+impl Schedule {
+    pub fn parse(expression: &str, timezone: Tz) -> Result<Self, DomainError> {
+        let expression = expression.trim();
+        let kind = match expression.strip_prefix("@every") {
+            Some(interval) => ScheduleKind::Interval(parse_interval(interval.trim())?),
+            None => ScheduleKind::Cron(Box::new(cron::Schedule::from_str(expression).map_err(|err| {
+                DomainError::BadRequest(format!("Invalid cron expression '{}': {}", expression, err))
+            })?)),
+        };
+
+        Ok(Self {
+            expression: expression.to_string(),
+            kind,
+            timezone,
+        })
+    }
+
+    /// Next fire time strictly after `after`.
+    ///
+    /// Cron fields are matched against local wall-clock time. A time skipped by
+    /// a spring-forward transition fires as soon as the clock resumes, and a
+    /// time repeated by a fall-back transition fires only once (on its first pass).
+    pub fn next_after(&self, after: &DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match &self.kind {
+            ScheduleKind::Interval(interval) => {
+                after.checked_add_signed(chrono::Duration::from_std(*interval).ok()?)
+            }
+            ScheduleKind::Cron(schedule) => {
+                let local_after = Utc.from_utc_datetime(&after.with_timezone(&self.timezone).naive_local());
+
+                schedule
+                    .after(&local_after)
+                    .filter_map(|candidate| self.resolve_local(candidate.naive_utc()))
+                    .find(|candidate| candidate > after)
+            }
+        }
+    }
+
+    /// Whether a fire time was missed between `last_run` and `now`, counting
+    /// only the ones that are no older than `grace`.
+    pub fn missed_since(&self, last_run: &DateTime<Utc>, now: &DateTime<Utc>, grace: Duration) -> bool {
+        let grace = chrono::Duration::from_std(grace).unwrap_or(chrono::Duration::MAX);
+        let window_start = now.checked_sub_signed(grace).map_or(*last_run, |start| start.max(*last_run));
+
+        self.next_after(&window_start).is_some_and(|next| next <= *now)
+    }
+
+    pub fn duration_until_next(&self) -> Result<Duration, DomainError> {
+        let now = Utc::now();
+        let next = self.next_after(&now).ok_or_else(|| {
+            DomainError::BadRequest(format!("Schedule '{}' has no upcoming run", self.expression))
+        })?;
+
+        Ok((next - now).to_std().unwrap_or(Duration::ZERO))
+    }
+
+    fn resolve_local(&self, naive: NaiveDateTime) -> Option<DateTime<Utc>> {
+        match self.timezone.from_local_datetime(&naive) {
+            LocalResult::Single(datetime) => Some(datetime.with_timezone(&Utc)),
+            LocalResult::Ambiguous(earliest, _) => Some(earliest.with_timezone(&Utc)),
+            LocalResult::None => (1..=MAX_GAP_MINUTES)
+                .map(|minutes| naive + chrono::Duration::minutes(minutes))
+                .find_map(|shifted| self.timezone.from_local_datetime(&shifted).earliest())
+                .map(|datetime| {
+                    // Fire at the first instant after the gap rather than minutes into it
+                    let gap_end = datetime.with_timezone(&Utc);
+                    gap_end - chrono::Duration::seconds(gap_end.timestamp() % 60)
+                }),
+        }
+    }
+}
+
+fn parse_interval(interval: &str) -> Result<Duration, DomainError> {
+    let invalid = || DomainError::BadRequest(format!("Invalid interval '{}', expected e.g. 30m, 2h or 1d", interval));
+
+    let split_at = interval.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let (amount, unit) = interval.split_at(split_at);
+    let amount: u64 = amount.parse().map_err(|_| invalid())?;
+
+    let unit_seconds: u64 = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 24 * 3600,
+        _ => return Err(invalid()),
+    };
+
+    let seconds = amount.checked_mul(unit_seconds).ok_or_else(|| {
+        DomainError::BadRequest(format!("Interval '{}' is too long", interval))
+    })?;
+    if seconds == 0 {
+        return Err(invalid());
+    }
+
+    Ok(Duration::from_secs(seconds))
+}
+
+pub fn parse_timezone(timezone: &str) -> Result<Tz, DomainError> {
+    timezone
+        .parse::<Tz>()
+        .map_err(|err| DomainError::BadRequest(format!("Invalid timezone '{}': {}", timezone, err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn toronto() -> Tz {
+        parse_timezone("America/Toronto").unwrap()
+    }
+
+    fn utc(datetime: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(datetime).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn it_should_fire_daily_at_local_hour() {
+        let schedule = Schedule::parse("0 0 1 * * *", toronto()).unwrap();
+
+        let next = schedule.next_after(&utc("2024-06-01T12:00:00Z")).unwrap();
+
+        assert_eq!(next, utc("2024-06-02T05:00:00Z"));
+    }
+
+    #[test]
+    fn it_should_keep_local_hour_across_dst_transitions() {
+        let schedule = Schedule::parse("0 0 1 * * *", toronto()).unwrap();
+
+        let before_spring = schedule.next_after(&utc("2024-03-09T12:00:00Z")).unwrap();
+        let after_spring = schedule.next_after(&before_spring).unwrap();
+        assert_eq!(before_spring, utc("2024-03-10T06:00:00Z"));
+        assert_eq!(after_spring, utc("2024-03-11T05:00:00Z"));
+
+        let before_fall = schedule.next_after(&utc("2024-11-02T12:00:00Z")).unwrap();
+        let after_fall = schedule.next_after(&before_fall).unwrap();
+        assert_eq!(before_fall, utc("2024-11-03T05:00:00Z"));
+        assert_eq!(after_fall, utc("2024-11-04T06:00:00Z"));
+    }
+
+    #[test]
+    fn it_should_fire_after_spring_forward_gap() {
+        let schedule = Schedule::parse("0 30 2 * * *", toronto()).unwrap();
+
+        // 02:30 does not exist on 2024-03-10, the clock jumps from 02:00 to 03:00 EDT
+        let next = schedule.next_after(&utc("2024-03-10T05:00:00Z")).unwrap();
+
+        assert_eq!(next, utc("2024-03-10T07:00:00Z"));
+        assert_eq!(schedule.next_after(&next).unwrap(), utc("2024-03-11T06:30:00Z"));
+    }
+
+    #[test]
+    fn it_should_fire_once_during_fall_back_overlap() {
+        let schedule = Schedule::parse("0 30 1 * * *", toronto()).unwrap();
+
+        // 01:30 happens twice on 2024-11-03, first in EDT then in EST
+        let next = schedule.next_after(&utc("2024-11-03T04:00:00Z")).unwrap();
+
+        assert_eq!(next, utc("2024-11-03T05:30:00Z"));
+        assert_eq!(schedule.next_after(&next).unwrap(), utc("2024-11-04T06:30:00Z"));
+    }
+
+    #[test]
+    fn it_should_restrict_to_weekdays() {
+        let schedule = Schedule::parse("0 0 */2 * * Mon-Fri", toronto()).unwrap();
+
+        // Saturday noon in Toronto
+        let next = schedule.next_after(&utc("2024-06-01T16:00:00Z")).unwrap();
+
+        assert_eq!(next, utc("2024-06-03T04:00:00Z"));
+    }
+
+    #[test]
+    fn it_should_fire_on_interval() {
+        let schedule = Schedule::parse("@every 2h", toronto()).unwrap();
+
+        let next = schedule.next_after(&utc("2024-03-10T06:30:00Z")).unwrap();
+
+        assert_eq!(next, utc("2024-03-10T08:30:00Z"));
+    }
+
+    #[test]
+    fn it_should_detect_run_missed_during_restart() {
+        let schedule = Schedule::parse("0 0 1 * * *", toronto()).unwrap();
+        let last_run = utc("2024-06-01T05:00:00Z");
+        let now = utc("2024-06-02T05:30:00Z");
+
+        assert!(schedule.missed_since(&last_run, &now, Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn it_should_not_detect_run_before_next_fire_time() {
+        let schedule = Schedule::parse("0 0 1 * * *", toronto()).unwrap();
+        let last_run = utc("2024-06-02T05:00:00Z");
+        let now = utc("2024-06-02T12:00:00Z");
+
+        assert!(!schedule.missed_since(&last_run, &now, Duration::from_secs(86400)));
+    }
+
+    #[test]
+    fn it_should_not_detect_run_missed_outside_grace_window() {
+        let schedule = Schedule::parse("0 0 1 * * *", toronto()).unwrap();
+        let last_run = utc("2024-06-01T05:00:00Z");
+        let now = utc("2024-06-02T12:00:00Z");
+
+        assert!(!schedule.missed_since(&last_run, &now, Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn it_should_return_error_invalid_expression() {
+        assert!(matches!(Schedule::parse("every day", toronto()), Err(DomainError::BadRequest(_))));
+        assert!(matches!(Schedule::parse("@every 0h", toronto()), Err(DomainError::BadRequest(_))));
+        assert!(matches!(Schedule::parse("@every 2w", toronto()), Err(DomainError::BadRequest(_))));
+        assert!(matches!(parse_timezone("Mars/Olympus"), Err(DomainError::BadRequest(_))));
+    }
+
+    #[test]
+    fn it_should_return_error_interval_too_long() {
+        assert!(matches!(Schedule::parse("@every 18446744073709551615d", toronto()), Err(DomainError::BadRequest(_))));
+
+        // Parsed, but past the last date chrono represents
+        let schedule = Schedule::parse("@every 9999999999999h", toronto()).unwrap();
+        assert_eq!(schedule.next_after(&utc("2024-06-01T12:00:00Z")), None);
+        assert!(matches!(schedule.duration_until_next(), Err(DomainError::BadRequest(_))));
+    }
+}
@@ -1,58 +1,130 @@
 use std::sync::Arc;
 
-use tokio::time::Interval;
+use async_trait::async_trait;
+use tracing::Instrument;
 
-use crate::{domain::{article::{adapter::ArticleAdapter, repository::ArticleRepository, model::{ArticleCreateModel, ArticleModel, ArticleUpdateModel, Guidable, Processable}}, diff_article::{repository::DiffArticleRepository, model::DiffArticleModel}, error::DomainError, registered_author::{repository::{ RegisteredAuthorRepository}, model::RegisteredAuthorModel}}, api::lib::{BatchOperations, DiffOperations}};
+use crate::{domain::{article::{adapter::ArticleAdapter, repository::ArticleRepository, model::{ArticleCreateModel, ArticleModel, ArticleUpdateModel, Guidable, Processable}}, diff_article::{repository::DiffArticleRepository, model::DiffArticleModel}, dead_letter::{model::{FetchFailure, Fetched}, repository::DeadLetterRepository}, error::DomainError, field_change::{model::{FieldChange, FieldChanges}, repository::FieldChangeRepository}, sync_run::model::SyncReport, registered_author::{repository::{ RegisteredAuthorRepository}, model::RegisteredAuthorModel}}, api::{engine::{SyncEngine, SyncItem}, lib::{CurrentOperations, SyncContext, SyncJob}, registry::AdapterRegistry}};
 
+// Job name in schedules, on the command line and in metrics
+const JOB: &str = "articles";
 
+// Entity name in dry run reports and traces
+const ENTITY: &str = "article";
 
-pub struct ArticleSync<A: ArticleAdapter> {
-    adapter: A,
-    repository: Arc<dyn ArticleRepository>,
-    diff_repository: Arc<dyn DiffArticleRepository>,
+pub struct ArticleSync {
+    adapters: AdapterRegistry<dyn ArticleAdapter>,
+    engine: SyncEngine<ArticleCreateModel, dyn ArticleRepository, dyn DiffArticleRepository>,
     registered_author_repository: Arc<dyn RegisteredAuthorRepository>,
-    interval: Interval,
 }
 
-impl<A: ArticleAdapter> ArticleSync<A> {
+impl ArticleSync {
     pub fn new(
-        adapter: A,
+        adapters: AdapterRegistry<dyn ArticleAdapter>,
         repository: Arc<dyn ArticleRepository>,
         diff_repository: Arc<dyn DiffArticleRepository>,
         registered_author_repository: Arc<dyn RegisteredAuthorRepository>,
-        interval: Interval
-    ) -> ArticleSync<A> {
+        dead_letter_repository: Arc<dyn DeadLetterRepository>,
+        field_change_repository: Arc<dyn FieldChangeRepository>,
+        delete_after_runs: i32,
+    ) -> ArticleSync {
         ArticleSync {
-            adapter,
-            interval,
-            repository,
-            diff_repository,
+            adapters,
+            engine: SyncEngine::new(JOB, ENTITY, repository, diff_repository)
+                .with_delete_after_runs(delete_after_runs)
+                .with_dead_letters(dead_letter_repository)
+                .with_history(field_change_repository),
             registered_author_repository,
         }
     }
 
-    pub async fn start(&mut self) -> Result<(), DomainError> {
-        loop {
-            // Wait until the next interval
-            self.interval.tick().await;
-
-            // Perform the sync
-            self.sync().await?;
+    async fn process_articles(&self, author: &RegisteredAuthorModel, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        let source = author.source.as_str();
+        let span = tracing::info_span!("author", source, author = %author.name);
+        async {
+            // A failed author is dead-lettered, so the other authors still
+            // sync, as is an author of a source without adapter
+            let adapter = match self.adapters.get(source) {
+                Ok(adapter) => adapter,
+                Err(err) => {
+                    let failures = vec![FetchFailure::scope(source, &author.name, err)];
+                    self.engine.dead_letter(source, std::slice::from_ref(&author.name), failures, context).await?;
+                    return Ok(SyncReport::default());
+                }
+            };
+            let fetched = match adapter.fetch(author).instrument(tracing::info_span!("fetch", entity = ENTITY)).await {
+                Ok(fetched) => fetched,
+                Err(err) => Fetched {
+                    items: Vec::new(),
+                    failures: vec![FetchFailure::scope(source, &author.name, err)],
+                },
+            };
+            // Feeds only list the latest articles, so only those published
+            // since the oldest one listed can be gone, and a failed article or
+            // page may not be gone either
+            if fetched.is_complete(&author.name) {
+                if adapter.lists_all() {
+                    context.complete_scope(source, &author.name);
+                } else if let Some(oldest) = fetched.items.iter().map(|article| article.publish_at).min() {
+                    context.complete_window(source, &author.name, oldest);
+                }
+            }
+            self.engine.dead_letter(source, std::slice::from_ref(&author.name), fetched.failures, context).await?;
+            self.engine.process(fetched.items, source, context).await
         }
+        .instrument(span)
+        .await
+    }
+    
+    async fn get_authors(&self, page: u32, size: u32) -> Result<Option<(Vec<RegisteredAuthorModel>, u32)>, DomainError> {
+        self.registered_author_repository.find(&None, &page, &size).await
+    }
+    
+    async fn process_all_articles(&self, authors: Vec<RegisteredAuthorModel>, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        let mut report = SyncReport::default();
+        for author in authors {
+            if context.is_shutdown_requested() {
+                break;
+            }
+            if !context.includes_author(&author.source, &author.name) {
+                continue;
+            }
+            report += self.process_articles(&author, context).await?;
+        }
+        Ok(report)
+    }
+    
+    fn is_end_of_authors(&self, total_processed: u32, total_authors: u32) -> bool {
+        total_processed >= total_authors
+    }
+}
+
+#[async_trait]
+impl SyncJob for ArticleSync {
+    fn name(&self) -> &'static str {
+        JOB
     }
 
-    async fn sync(&self) -> Result<(), DomainError> {
-        println!("Syncing articles...");
+    async fn sync(&self, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        tracing::info!("Syncing articles...");
     
         let page_size = 100;
         let mut current_page = 1;
         let mut total = 0;
+        let mut report = SyncReport::default();
     
         loop {
+            // Stop between batches, so a batch is never written halfway
+            if context.is_shutdown_requested() {
+                tracing::info!("Shutdown requested, stopping articles sync");
+                break;
+            }
+
             let authors_data = self.get_authors(current_page, page_size).await?;
             if let Some((authors, total_authors)) = authors_data {
                 total += authors.len();
-                self.process_all_articles(authors).await?;
+                report += self.process_all_articles(authors, context)
+                    .instrument(tracing::info_span!("batch", page = current_page))
+                    .await?;
                 if self.is_end_of_authors(total as u32, total_authors) {
                     break;
                 } else {
@@ -62,98 +134,59 @@ impl<A: ArticleAdapter> ArticleSync<A> {
                 break;
             }
         }
-    
-        Ok(())
-    }
 
-    async fn process_articles(&self, author: String) -> Result<(), DomainError> {
-        let articles = self.adapter.fetch(author).await?;
-        self.process_items(articles).await?;
-        Ok(())
-    }
-    
-    async fn get_authors(&self, page: u32, size: u32) -> Result<Option<(Vec<RegisteredAuthorModel>, u32)>, DomainError> {
-        self.registered_author_repository.find(&None, &page, &size).await
-    }
-    
-    async fn process_all_articles(&self, authors: Vec<RegisteredAuthorModel>) -> Result<(), DomainError> {
-        for author in authors {
-            self.process_articles(author.name).await?;
+        // Only a complete pass tells which items are gone from their source
+        if !context.is_shutdown_requested() {
+            report += self.engine.sweep(context).await?;
         }
-        Ok(())
-    }
-    
-    fn is_end_of_authors(&self, total_processed: u32, total_authors: u32) -> bool {
-        total_processed >= total_authors
+
+        Ok(report)
     }
+}
 
-    async fn process_items(&self, items: Vec<ArticleCreateModel>) -> Result<(), DomainError> 
-    where
-        ArticleCreateModel: Processable + Guidable,
-        dyn ArticleRepository: BatchOperations<ArticleCreateModel, ArticleUpdateModel, ArticleModel>, 
-        dyn DiffArticleRepository: BatchOperations<DiffArticleModel, DiffArticleModel, DiffArticleModel> + DiffOperations<DiffArticleModel>
-    {
-        let extids: Vec<String> = items.iter().map(|item| item.extid.clone()).collect();
-        let existing_items = &self.diff_repository.find_by_extids(extids).await?;
-
-        let mut items_to_insert: Vec<ArticleCreateModel> = Vec::new();
-        let mut diff_items_to_insert: Vec<DiffArticleModel> = Vec::new();
-        let mut items_to_update: Vec<ArticleUpdateModel> = Vec::new();
-        let mut diff_items_to_update: Vec<DiffArticleModel> = Vec::new();
-
-        for item in items {
-            match existing_items.iter().find(|existing_item| existing_item.get_extid() == item.get_extid()) {
-                Some(existing_item) => {
-                    self.process_existing_item(&item, existing_item, &mut items_to_update, &mut diff_items_to_update)?;
-                }
-                None => {
-                    self.process_new_item(&item, &mut items_to_insert, &mut diff_items_to_insert)?;
-                }
-            }
-        }
+impl SyncItem for ArticleCreateModel {
+    type Update = ArticleUpdateModel;
+    type Current = ArticleModel;
+    type Diff = DiffArticleModel;
 
-        self.insert_items(&items_to_insert, &diff_items_to_insert).await?;
-        self.update_items(&items_to_update, &diff_items_to_update).await?;
+    fn to_update(&self) -> Self::Update {
+        ArticleCreateModel::to_update(self)
+    }
 
-        Ok(())
+    fn to_diff(&self) -> Self::Diff {
+        DiffArticleModel::new(self.get_extid(), self.get_checksum())
+            .with_tags(self.tags.clone())
+            .with_edited_at(self.edited_at)
+            .with_publish_at(self.publish_at)
     }
 
-    fn process_existing_item(&self, 
-        item: &ArticleCreateModel, 
-        existing_item: &DiffArticleModel, 
-        items_to_update: &mut Vec<ArticleUpdateModel>, 
-        diff_items_to_update: &mut Vec<DiffArticleModel>
-    ) -> Result<(), DomainError> {
-        if existing_item.get_checksum() != item.get_checksum() {
-            items_to_update.push(item.to_update());
-            diff_items_to_update.push(DiffArticleModel::new(item.get_extid(),item.get_checksum()));
-        }
-        Ok(())
+    fn scope(&self) -> String {
+        self.author.clone()
     }
 
-    fn process_new_item(&self, 
-        item: &ArticleCreateModel, 
-        items_to_insert: &mut Vec<ArticleCreateModel>, 
-        diff_items_to_insert: &mut Vec<DiffArticleModel>
-    ) -> Result<(), DomainError> {
-        diff_items_to_insert.push(DiffArticleModel::new(item.get_extid(),item.get_checksum()));
-        items_to_insert.push(item.clone());
-        Ok(())
+    // Tags and edit dates are not stored in the main database
+    fn changed_sync_fields(&self, stored: Option<&Self::Diff>) -> Vec<FieldChange> {
+        let mut changes = FieldChanges::new();
+        changes
+            .compare("tags", stored.map(|stored| &stored.tags), &self.tags)
+            .compare("edited_at", stored.map(|stored| &stored.edited_at), &self.edited_at);
+        changes.into_vec()
     }
+}
 
-    async fn insert_items(&self, items_to_insert: &Vec<ArticleCreateModel>, diff_items_to_insert: &Vec<DiffArticleModel>) -> Result<(), DomainError> {
-        if !items_to_insert.is_empty() {
-            let _ = &self.repository.insert_many(items_to_insert.clone()).await?;
-            let _ = &self.diff_repository.insert_many(diff_items_to_insert.clone()).await?;
-        }
-        Ok(())
+#[async_trait]
+impl CurrentOperations<ArticleModel> for dyn ArticleRepository {
+    async fn find_current(&self, extid: &str) -> Result<Option<ArticleModel>, DomainError> {
+        self.find_by_extid(extid).await
     }
 
-    async fn update_items(&self, items_to_update: &Vec<ArticleUpdateModel>, diff_items_to_update: &Vec<DiffArticleModel>) -> Result<(), DomainError> {
-        if !items_to_update.is_empty() {
-            let _ = &self.repository.update_many(items_to_update.clone()).await?;
-            let _ = &self.diff_repository.update_many(diff_items_to_update.clone()).await?;
+    async fn delete_current(&self, extid: &str) -> Result<bool, DomainError> {
+        match self.find_by_extid(extid).await? {
+            Some(current) => {
+                self.delete_by_articleid(&current.articleid).await?;
+                Ok(true)
+            }
+            None => Ok(false),
         }
-        Ok(())
     }
 }
@@ -1,17 +1,24 @@
 use std::sync::Arc;
 
-use tokio::time::Interval;
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::Instrument;
 
-use crate::{domain::{event::{adapter::EventAdapter, repository::EventRepository, model::{EventCreateModel, EventModel, EventUpdateModel}}, diff_event::{repository::DiffEventRepository, model::DiffEventModel}, error::DomainError, article::model::{Processable, Guidable}, group::{repository::GroupRepository, model::GroupModel}}, api::lib::{BatchOperations, DiffOperations}};
+use crate::{domain::{event::{adapter::EventAdapter, repository::EventRepository, model::{EventCreateModel, EventModel, EventUpdateModel}}, diff_event::{repository::DiffEventRepository, model::DiffEventModel}, dead_letter::repository::DeadLetterRepository, error::DomainError, field_change::repository::FieldChangeRepository, sync_run::model::SyncReport, article::model::{Processable, Guidable}, group::{repository::GroupRepository, model::GroupModel}}, api::{engine::{SyncEngine, SyncItem}, lib::{CurrentOperations, SyncContext, SyncJob}}};
 
+// Job name in schedules, on the command line and in metrics
+const JOB: &str = "events";
 
+// Entity name in dry run reports and traces
+const ENTITY: &str = "event";
+
+// Source label of the items in metrics
+const SOURCE: &str = "meetup";
 
 pub struct EventSync<A: EventAdapter> {
     adapter: A,
-    repository: Arc<dyn EventRepository>,
-    diff_repository: Arc<dyn DiffEventRepository>,
+    engine: SyncEngine<EventCreateModel, dyn EventRepository, dyn DiffEventRepository>,
     group_repository: Arc<dyn GroupRepository>,
-    interval: Interval,
 }
 
 impl<A: EventAdapter> EventSync<A> {
@@ -20,39 +27,82 @@ impl<A: EventAdapter> EventSync<A> {
         repository: Arc<dyn EventRepository>,
         diff_repository: Arc<dyn DiffEventRepository>,
         group_repository: Arc<dyn GroupRepository>,
-        interval: Interval
+        dead_letter_repository: Arc<dyn DeadLetterRepository>,
+        field_change_repository: Arc<dyn FieldChangeRepository>,
+        delete_after_runs: i32,
     ) -> EventSync<A> {
         EventSync {
             adapter,
-            repository,
-            diff_repository,
+            engine: SyncEngine::new(JOB, ENTITY, repository, diff_repository)
+                .with_delete_after_runs(delete_after_runs)
+                .with_dead_letters(dead_letter_repository)
+                .with_history(field_change_repository),
             group_repository,
-            interval,
         }
     }
 
-    pub async fn start(&mut self) -> Result<(), DomainError> {
-        loop {
-            // Wait until the next interval
-            self.interval.tick().await;
-
-            // Perform the sync
-            self.sync().await?;
+    async fn process_events(&self, group_models: Vec<GroupModel>, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        let slugs: Vec<String> = group_models.iter().map(|group_model| group_model.slug.clone()).collect();
+        let fetched_at = Utc::now();
+        let fetched = self.adapter.fetch(group_models)
+            .instrument(tracing::info_span!("fetch", entity = ENTITY))
+            .await?;
+        // Meetup only lists the upcoming events, so only those still to come
+        // can be gone, unless the group failed
+        for slug in slugs.iter().filter(|slug| fetched.is_complete(slug)) {
+            context.complete_window(SOURCE, slug, fetched_at);
         }
+        self.engine.dead_letter(SOURCE, &slugs, fetched.failures, context).await?;
+        self.engine.process(fetched.items, SOURCE, context).await
+    }
+    
+    async fn get_authors(&self, page: u32, size: u32) -> Result<Option<(Vec<GroupModel>, u32)>, DomainError> {
+        self.group_repository.find(&None, &page, &size).await
+    }
+    
+    async fn process_all_events(&self, group_models: Vec<GroupModel>, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        let group_models: Vec<GroupModel> = group_models
+            .into_iter()
+            .filter(|group_model| context.includes_group(SOURCE, &group_model.slug))
+            .collect();
+        if group_models.is_empty() {
+            return Ok(SyncReport::default());
+        }
+        self.process_events(group_models, context).await
+    }
+    
+    fn is_end_of_authors(&self, total_processed: u32, total_authors: u32) -> bool {
+        total_processed >= total_authors
+    }
+}
+
+#[async_trait]
+impl<A: EventAdapter> SyncJob for EventSync<A> {
+    fn name(&self) -> &'static str {
+        JOB
     }
 
-    async fn sync(&self) -> Result<(), DomainError> {
-        println!("Syncing events...");
+    async fn sync(&self, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        tracing::info!("Syncing events...");
     
         let page_size = 100;
         let mut current_page = 1;
         let mut total = 0;
+        let mut report = SyncReport::default();
     
         loop {
+            // Stop between batches, so a batch is never written halfway
+            if context.is_shutdown_requested() {
+                tracing::info!("Shutdown requested, stopping events sync");
+                break;
+            }
+
             let authors_data = self.get_authors(current_page, page_size).await?;
             if let Some((authors, total_authors)) = authors_data {
                 total += authors.len();
-                self.process_all_events(authors).await?;
+                report += self.process_all_events(authors, context)
+                    .instrument(tracing::info_span!("batch", page = current_page))
+                    .await?;
                 if self.is_end_of_authors(total as u32, total_authors) {
                     break;
                 } else {
@@ -62,96 +112,47 @@ impl<A: EventAdapter> EventSync<A> {
                 break;
             }
         }
-    
-        Ok(())
-    }
 
-    async fn process_events(&self, group_models: Vec<GroupModel>) -> Result<(), DomainError> {
-        let events = self.adapter.fetch(group_models).await?;
-        self.process_items(events).await?;
-        Ok(())
-    }
-    
-    async fn get_authors(&self, page: u32, size: u32) -> Result<Option<(Vec<GroupModel>, u32)>, DomainError> {
-        self.group_repository.find(&None, &page, &size).await
-    }
-    
-    async fn process_all_events(&self, group_models: Vec<GroupModel>) -> Result<(), DomainError> {
-        self.process_events(group_models).await?;
-        Ok(())
-    }
-    
-    fn is_end_of_authors(&self, total_processed: u32, total_authors: u32) -> bool {
-        total_processed >= total_authors
-    }
-
-    async fn process_items(&self, items: Vec<EventCreateModel>) -> Result<(), DomainError> 
-    where
-        EventCreateModel: Processable + Guidable,
-        dyn EventRepository: BatchOperations<EventCreateModel, EventUpdateModel, EventModel>, 
-        dyn DiffEventRepository: BatchOperations<DiffEventModel, DiffEventModel, DiffEventModel> + DiffOperations<DiffEventModel>
-    {
-        let extids: Vec<String> = items.iter().map(|item| item.extid.clone()).collect();
-        let existing_items = &self.diff_repository.find_by_extids(extids).await?;
-
-        let mut items_to_insert: Vec<EventCreateModel> = Vec::new();
-        let mut diff_items_to_insert: Vec<DiffEventModel> = Vec::new();
-        let mut items_to_update: Vec<EventUpdateModel> = Vec::new();
-        let mut diff_items_to_update: Vec<DiffEventModel> = Vec::new();
-
-        for item in items {
-            match existing_items.iter().find(|existing_item| existing_item.get_extid() == item.get_extid()) {
-                Some(existing_item) => {
-                    self.process_existing_item(&item, existing_item, &mut items_to_update, &mut diff_items_to_update)?;
-                }
-                None => {
-                    self.process_new_item(&item, &mut items_to_insert, &mut diff_items_to_insert)?;
-                }
-            }
+        // Only a complete pass tells which items are gone from their source
+        if !context.is_shutdown_requested() {
+            report += self.engine.sweep(context).await?;
         }
 
-        self.insert_items(&items_to_insert, &diff_items_to_insert).await?;
-        self.update_items(&items_to_update, &diff_items_to_update).await?;
+        Ok(report)
+    }
+}
 
-        Ok(())
+impl SyncItem for EventCreateModel {
+    type Update = EventUpdateModel;
+    type Current = EventModel;
+    type Diff = DiffEventModel;
+
+    fn to_update(&self) -> Self::Update {
+        EventCreateModel::to_update(self)
     }
 
-    fn process_existing_item(&self, 
-        item: &EventCreateModel, 
-        existing_item: &DiffEventModel, 
-        items_to_update: &mut Vec<EventUpdateModel>, 
-        diff_items_to_update: &mut Vec<DiffEventModel>
-    ) -> Result<(), DomainError> {
-        if existing_item.get_checksum() != item.get_checksum() {
-            items_to_update.push(item.to_update());
-            diff_items_to_update.push(DiffEventModel::new(item.get_extid(),item.get_checksum()));
-        }
-        Ok(())
+    fn to_diff(&self) -> Self::Diff {
+        DiffEventModel::new(self.get_extid(), self.get_checksum()).with_time(self.time)
     }
 
-    fn process_new_item(&self, 
-        item: &EventCreateModel, 
-        items_to_insert: &mut Vec<EventCreateModel>, 
-        diff_items_to_insert: &mut Vec<DiffEventModel>
-    ) -> Result<(), DomainError> {
-        diff_items_to_insert.push(DiffEventModel::new(item.get_extid(),item.get_checksum()));
-        items_to_insert.push(item.clone());
-        Ok(())
+    fn scope(&self) -> String {
+        self.group_slug.clone()
     }
+}
 
-    async fn insert_items(&self, items_to_insert: &Vec<EventCreateModel>, diff_items_to_insert: &Vec<DiffEventModel>) -> Result<(), DomainError> {
-        if !items_to_insert.is_empty() {
-            let _ = &self.repository.insert_many(items_to_insert.clone()).await?;
-            let _ = &self.diff_repository.insert_many(diff_items_to_insert.clone()).await?;
-        }
-        Ok(())
+#[async_trait]
+impl CurrentOperations<EventModel> for dyn EventRepository {
+    async fn find_current(&self, extid: &str) -> Result<Option<EventModel>, DomainError> {
+        self.find_by_extid(extid.to_string()).await
     }
 
-    async fn update_items(&self, items_to_update: &Vec<EventUpdateModel>, diff_items_to_update: &Vec<DiffEventModel>) -> Result<(), DomainError> {
-        if !items_to_update.is_empty() {
-            let _ = &self.repository.update_many(items_to_update.clone()).await?;
-            let _ = &self.diff_repository.update_many(diff_items_to_update.clone()).await?;
+    async fn delete_current(&self, extid: &str) -> Result<bool, DomainError> {
+        match self.find_by_extid(extid.to_string()).await? {
+            Some(current) => {
+                self.delete_by_eventid(&current.eventid).await?;
+                Ok(true)
+            }
+            None => Ok(false),
         }
-        Ok(())
     }
 }
@@ -1,58 +1,116 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
-use tokio::time::Interval;
+use async_trait::async_trait;
+use tracing::Instrument;
 
-use crate::{domain::{group::{adapter::GroupAdapter, repository::GroupRepository, model::{GroupCreateModel, GroupModel, GroupUpdateModel}}, diff_group::{repository::DiffGroupRepository, model::DiffGroupModel}, error::DomainError,article::model::{Processable, Guidable}, registered_group::{repository::RegisteredGroupRepository, model::RegisteredGroupModel}}, api::lib::{BatchOperations, DiffOperations}};
+use crate::{domain::{group::{adapter::GroupAdapter, repository::GroupRepository, model::{GroupCreateModel, GroupModel, GroupUpdateModel}}, diff_group::{repository::DiffGroupRepository, model::DiffGroupModel}, dead_letter::{model::FetchFailure, repository::DeadLetterRepository}, error::DomainError, field_change::repository::FieldChangeRepository, sync_run::model::SyncReport,article::model::{Processable, Guidable}, registered_group::{repository::RegisteredGroupRepository, model::RegisteredGroupModel}}, api::{engine::{SyncEngine, SyncItem}, lib::{CurrentOperations, SyncContext, SyncJob}, registry::AdapterRegistry}};
 
+// Job name in schedules, on the command line and in metrics
+const JOB: &str = "groups";
 
+// Entity name in dry run reports and traces
+const ENTITY: &str = "group";
 
-pub struct GroupSync<A: GroupAdapter> {
-    adapter: A,
-    repository: Arc<dyn GroupRepository>,
-    diff_repository: Arc<dyn DiffGroupRepository>,
+pub struct GroupSync {
+    adapters: AdapterRegistry<dyn GroupAdapter>,
+    engine: SyncEngine<GroupCreateModel, dyn GroupRepository, dyn DiffGroupRepository>,
     registered_group_repository: Arc<dyn RegisteredGroupRepository>,
-    interval: Interval,
 }
 
-impl<A: GroupAdapter> GroupSync<A> {
+impl GroupSync {
     pub fn new(
-        adapter: A,
+        adapters: AdapterRegistry<dyn GroupAdapter>,
         repository: Arc<dyn GroupRepository>,
         diff_repository: Arc<dyn DiffGroupRepository>,
         registered_group_repository: Arc<dyn RegisteredGroupRepository>,
-        interval: Interval
-    ) -> GroupSync<A> {
+        dead_letter_repository: Arc<dyn DeadLetterRepository>,
+        field_change_repository: Arc<dyn FieldChangeRepository>,
+        delete_after_runs: i32,
+    ) -> GroupSync {
         GroupSync {
-            adapter,
-            repository,
-            diff_repository,
+            adapters,
+            engine: SyncEngine::new(JOB, ENTITY, repository, diff_repository)
+                .with_delete_after_runs(delete_after_runs)
+                .with_dead_letters(dead_letter_repository)
+                .with_history(field_change_repository),
             registered_group_repository,
-            interval,
         }
     }
 
-    pub async fn start(&mut self) -> Result<(), DomainError> {
-        loop {
-            // Wait until the next interval
-            self.interval.tick().await;
+    async fn process_groups(&self, source: &str, group_names: Vec<String>, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        // The groups of a source without adapter are dead-lettered, so the
+        // other sources still sync
+        let adapter = match self.adapters.get(source) {
+            Ok(adapter) => adapter,
+            Err(err) => {
+                let failures = group_names.iter().map(|name| FetchFailure::scope(source, name, &err)).collect();
+                self.engine.dead_letter(source, &group_names, failures, context).await?;
+                return Ok(SyncReport::default());
+            }
+        };
+        let fetched = adapter
+            .fetch(group_names.clone())
+            .instrument(tracing::info_span!("fetch", entity = ENTITY, source))
+            .await?;
+        // A group is the only item of its scope, unless it failed
+        for name in group_names.iter().filter(|name| fetched.is_complete(name)) {
+            context.complete_scope(source, name);
+        }
+        self.engine.dead_letter(source, &group_names, fetched.failures, context).await?;
+        self.engine.process(fetched.items, source, context).await
+    }
+    
+    async fn get_registred_groups(&self, page: u32, size: u32) -> Result<Option<(Vec<RegisteredGroupModel>, u32)>, DomainError> {
+        self.registered_group_repository.find(&None, &page, &size).await
+    }
+    
+    async fn process_all_groups(&self, registred_groups: Vec<RegisteredGroupModel>, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        let mut group_names_by_source: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for item in registred_groups {
+            if context.includes_group(&item.source, &item.name) {
+                group_names_by_source.entry(item.source).or_default().push(item.name);
+            }
+        }
 
-            // Perform the sync
-            self.sync().await?;
+        let mut report = SyncReport::default();
+        for (source, group_names) in group_names_by_source {
+            report += self.process_groups(&source, group_names, context).await?;
         }
+        Ok(report)
+    }
+    
+    fn is_end_of_registred_groups(&self, total_processed: u32, total_registred_groups: u32) -> bool {
+        total_processed >= total_registred_groups
+    }
+}
+
+#[async_trait]
+impl SyncJob for GroupSync {
+    fn name(&self) -> &'static str {
+        JOB
     }
 
-    async fn sync(&self) -> Result<(), DomainError> {
-        println!("Syncing groups...");
+    async fn sync(&self, context: &SyncContext) -> Result<SyncReport, DomainError> {
+        tracing::info!("Syncing groups...");
     
         let page_size = 100;
         let mut current_page = 1;
         let mut total = 0;
+        let mut report = SyncReport::default();
     
         loop {
+            // Stop between batches, so a batch is never written halfway
+            if context.is_shutdown_requested() {
+                tracing::info!("Shutdown requested, stopping groups sync");
+                break;
+            }
+
             let registred_groups_data = self.get_registred_groups(current_page, page_size).await?;
             if let Some((registred_groups, total_registred_groups)) = registred_groups_data {
                 total += registred_groups.len();
-                self.process_all_groups(registred_groups).await?;
+                report += self.process_all_groups(registred_groups, context)
+                    .instrument(tracing::info_span!("batch", page = current_page))
+                    .await?;
                 if self.is_end_of_registred_groups(total as u32, total_registred_groups) {
                     break;
                 } else {
@@ -62,97 +120,212 @@ impl<A: GroupAdapter> GroupSync<A> {
                 break;
             }
         }
-    
-        Ok(())
+
+        // Only a complete pass tells which items are gone from their source
+        if !context.is_shutdown_requested() {
+            report += self.engine.sweep(context).await?;
+        }
+
+        Ok(report)
     }
+}
+
+impl SyncItem for GroupCreateModel {
+    type Update = GroupUpdateModel;
+    type Current = GroupModel;
+    type Diff = DiffGroupModel;
 
-    async fn process_groups(&self, group_names: Vec<String>) -> Result<(), DomainError> {
-        let groups = self.adapter.fetch(group_names).await?;
-        self.process_items(groups).await?;
-        Ok(())
+    fn to_update(&self) -> Self::Update {
+        GroupCreateModel::to_update(self)
     }
-    
-    async fn get_registred_groups(&self, page: u32, size: u32) -> Result<Option<(Vec<RegisteredGroupModel>, u32)>, DomainError> {
-        self.registered_group_repository.find(&None, &page, &size).await
+
+    fn to_diff(&self) -> Self::Diff {
+        DiffGroupModel::new(self.get_extid(), self.get_checksum())
     }
-    
-    async fn process_all_groups(&self, registred_groups: Vec<RegisteredGroupModel>) -> Result<(), DomainError> {
-        let group_names: Vec<String> = registred_groups.into_iter().map(|item| item.name).collect();
-        self.process_groups(group_names).await?;
-        Ok(())
+
+    fn scope(&self) -> String {
+        self.registered_name.clone()
     }
-    
-    fn is_end_of_registred_groups(&self, total_processed: u32, total_registred_groups: u32) -> bool {
-        total_processed >= total_registred_groups
+}
+
+#[async_trait]
+impl CurrentOperations<GroupModel> for dyn GroupRepository {
+    async fn find_current(&self, extid: &str) -> Result<Option<GroupModel>, DomainError> {
+        self.find_by_extid(extid).await
     }
 
-    async fn process_items(&self, items: Vec<GroupCreateModel>) -> Result<(), DomainError> 
-    where
-        GroupCreateModel: Processable + Guidable,
-        dyn GroupRepository: BatchOperations<GroupCreateModel, GroupUpdateModel, GroupModel>, 
-        dyn DiffGroupRepository: BatchOperations<DiffGroupModel, DiffGroupModel, DiffGroupModel> + DiffOperations<DiffGroupModel>
-    {
-        let extids: Vec<String> = items.iter().map(|item| item.extid.clone()).collect();
-        let existing_items = &self.diff_repository.find_by_extids(extids).await?;
-
-        let mut items_to_insert: Vec<GroupCreateModel> = Vec::new();
-        let mut diff_items_to_insert: Vec<DiffGroupModel> = Vec::new();
-        let mut items_to_update: Vec<GroupUpdateModel> = Vec::new();
-        let mut diff_items_to_update: Vec<DiffGroupModel> = Vec::new();
-
-        for item in items {
-            match existing_items.iter().find(|existing_item| existing_item.get_extid() == item.get_extid()) {
-                Some(existing_item) => {
-                    self.process_existing_item(&item, existing_item, &mut items_to_update, &mut diff_items_to_update)?;
-                }
-                None => {
-                    self.process_new_item(&item, &mut items_to_insert, &mut diff_items_to_insert)?;
-                }
+    async fn delete_current(&self, extid: &str) -> Result<bool, DomainError> {
+        match self.find_by_extid(extid).await? {
+            Some(current) => {
+                self.delete_by_groupid(&current.groupid).await?;
+                Ok(true)
             }
+            None => Ok(false),
         }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{DateTime, Utc};
+    use mockall::mock;
+
+    use super::*;
 
-        self.insert_items(&items_to_insert, &diff_items_to_insert).await?;
-        self.update_items(&items_to_update, &diff_items_to_update).await?;
+    use crate::{api::{lib::{BatchOperations, DiffOperations}, shutdown::Shutdown}, domain::{dead_letter::model::{DeadLetterCreateModel, DeadLetterModel, FetchFailure, Fetched}, diff_group::model::DiffGroupModel, field_change::model::{FieldChangeCreateModel, FieldChangeModel}, group::model::GroupUpdateModel, registered_group::model::RegisteredGroupCreateModel}};
 
-        Ok(())
+    mock! {
+        pub FakeGroupAdapter { }
+
+        #[async_trait]
+        impl GroupAdapter for FakeGroupAdapter {
+            async fn fetch(&self, names: Vec<String>) -> Result<Fetched<GroupCreateModel>, DomainError>;
+        }
     }
 
-    fn process_existing_item(&self, 
-        item: &GroupCreateModel, 
-        existing_item: &DiffGroupModel, 
-        items_to_update: &mut Vec<GroupUpdateModel>, 
-        diff_items_to_update: &mut Vec<DiffGroupModel>
-    ) -> Result<(), DomainError> {
-        if existing_item.get_checksum() != item.get_checksum() {
-            items_to_update.push(item.to_update());
-            diff_items_to_update.push(DiffGroupModel::new(item.get_extid(),item.get_checksum()));
+    mock! {
+        pub FakeGroupRepository { }
+
+        #[async_trait]
+        impl GroupRepository for FakeGroupRepository {
+            async fn find(&self, name: &Option<String>, page: &u32, page_size: &u32) -> Result<Option<(Vec<GroupModel>, u32)>, DomainError>;
+            async fn find_by_groupid(&self, id: &i32) -> Result<Option<GroupModel>, DomainError>;
+            async fn find_by_extid(&self, extid: &str) -> Result<Option<GroupModel>, DomainError>;
+            async fn insert(&self, group_create_model: &GroupCreateModel) -> Result<GroupModel, DomainError>;
+            async fn update_by_extid(&self, group_update_model: &GroupUpdateModel) -> Result<GroupModel, DomainError>;
+            async fn delete_by_groupid(&self, id: &i32) -> Result<(), DomainError>;
+        }
+
+        #[async_trait]
+        impl BatchOperations<GroupCreateModel, GroupUpdateModel, GroupModel> for FakeGroupRepository {
+            async fn insert_many(&self, items: Vec<GroupCreateModel>) -> Result<Vec<GroupModel>, DomainError>;
+            async fn update_many(&self, items: Vec<GroupUpdateModel>) -> Result<Vec<GroupModel>, DomainError>;
         }
-        Ok(())
     }
 
-    fn process_new_item(&self, 
-        item: &GroupCreateModel, 
-        items_to_insert: &mut Vec<GroupCreateModel>, 
-        diff_items_to_insert: &mut Vec<DiffGroupModel>
-    ) -> Result<(), DomainError> {
-        diff_items_to_insert.push(DiffGroupModel::new(item.get_extid(),item.get_checksum()));
-        items_to_insert.push(item.clone());
-        Ok(())
+    mock! {
+        pub FakeDiffGroupRepository { }
+
+        #[async_trait]
+        impl DiffGroupRepository for FakeDiffGroupRepository { }
+
+        #[async_trait]
+        impl BatchOperations<DiffGroupModel, DiffGroupModel, DiffGroupModel> for FakeDiffGroupRepository {
+            async fn insert_many(&self, items: Vec<DiffGroupModel>) -> Result<Vec<DiffGroupModel>, DomainError>;
+            async fn update_many(&self, items: Vec<DiffGroupModel>) -> Result<Vec<DiffGroupModel>, DomainError>;
+        }
+
+        #[async_trait]
+        impl DiffOperations<DiffGroupModel> for FakeDiffGroupRepository {
+            async fn find_by_extids(&self, extids: Vec<String>) -> Result<Vec<DiffGroupModel>, DomainError>;
+            async fn touch_many(&self, extids: Vec<String>, source: &str, scope: &str, run_id: i32) -> Result<(), DomainError>;
+            async fn mark_unseen(&self, run_id: i32, source: &str, scopes: Vec<String>, since: Option<DateTime<Utc>>, missed_runs: i32) -> Result<Vec<String>, DomainError>;
+            async fn delete_by_extids(&self, extids: Vec<String>) -> Result<(), DomainError>;
+        }
     }
 
-    async fn insert_items(&self, items_to_insert: &Vec<GroupCreateModel>, diff_items_to_insert: &Vec<DiffGroupModel>) -> Result<(), DomainError> {
-        if !items_to_insert.is_empty() {
-            let _ = &self.repository.insert_many(items_to_insert.clone()).await?;
-            let _ = &self.diff_repository.insert_many(diff_items_to_insert.clone()).await?;
+    mock! {
+        pub FakeRegisteredGroupRepository { }
+
+        #[async_trait]
+        impl RegisteredGroupRepository for FakeRegisteredGroupRepository {
+            async fn find(&self, name: &Option<String>, page: &u32, page_size: &u32) -> Result<Option<(Vec<RegisteredGroupModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<RegisteredGroupModel>, DomainError>;
+            async fn insert(&self, registered_group_create_model: &RegisteredGroupCreateModel) -> Result<RegisteredGroupModel, DomainError>;
+            async fn delete_by_id(&self, id: &i32) -> Result<(), DomainError>;
         }
-        Ok(())
     }
 
-    async fn update_items(&self, items_to_update: &Vec<GroupUpdateModel>, diff_items_to_update: &Vec<DiffGroupModel>) -> Result<(), DomainError> {
-        if !items_to_update.is_empty() {
-            let _ = &self.repository.update_many(items_to_update.clone()).await?;
-            let _ = &self.diff_repository.update_many(diff_items_to_update.clone()).await?;
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self, job: &Option<String>, page: &u32, page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<DeadLetterModel>, DomainError>;
+            async fn upsert(&self, dead_letter_create_model: &DeadLetterCreateModel) -> Result<DeadLetterModel, DomainError>;
+            async fn delete_resolved(&self, job: &str, source: &str, scopes: &[String], keys: &[String]) -> Result<(), DomainError>;
         }
-        Ok(())
+    }
+
+    mock! {
+        pub FakeFieldChangeRepository { }
+
+        #[async_trait]
+        impl FieldChangeRepository for FakeFieldChangeRepository {
+            async fn find_by_extid(&self, entity: &str, extid: &str) -> Result<Vec<FieldChangeModel>, DomainError>;
+            async fn insert_many(&self, changes: &[FieldChangeCreateModel]) -> Result<(), DomainError>;
+        }
+    }
+
+    fn registered_group(name: &str) -> RegisteredGroupModel {
+        let mut registered_group = RegisteredGroupModel::mock_default();
+        registered_group.source = "meetup".to_string();
+        registered_group.name = name.to_string();
+        registered_group
+    }
+
+    #[tokio::test]
+    async fn it_should_scope_groups_by_registered_name() {
+        let mut adapter = MockFakeGroupAdapter::new();
+        adapter.expect_fetch().returning(|_| {
+            let mut group = GroupCreateModel::mock_default().with_registered_name("Rust-Montreal");
+            group.slug = "rust-montreal".to_string();
+            Ok(Fetched {
+                items: vec![group],
+                failures: vec![FetchFailure::scope("meetup", "Data-Quebec", "Failed to fetch group from Meetup API")],
+            })
+        });
+
+        let mut registered_group_repository = MockFakeRegisteredGroupRepository::new();
+        registered_group_repository
+            .expect_find()
+            .returning(|_, _, _| Ok(Some((vec![registered_group("Rust-Montreal"), registered_group("Data-Quebec")], 2))));
+
+        let mut repository = MockFakeGroupRepository::new();
+        repository.expect_insert_many().returning(|_| Ok(Vec::new()));
+
+        let mut diff_repository = MockFakeDiffGroupRepository::new();
+        diff_repository.expect_find_by_extids().returning(|_| Ok(Vec::new()));
+        diff_repository.expect_insert_many().returning(Ok);
+        diff_repository
+            .expect_touch_many()
+            .withf(|_, source, scope, run_id| source == "meetup" && scope == "Rust-Montreal" && *run_id == 7)
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+        // The failed group is left alone
+        diff_repository
+            .expect_mark_unseen()
+            .withf(|_, source, scopes, since, _| source == "meetup" && scopes == &["Rust-Montreal"] && since.is_none())
+            .times(1)
+            .returning(|_, _, _, _, _| Ok(Vec::new()));
+
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_upsert()
+            .withf(|dead_letter| dead_letter.scope == "Data-Quebec")
+            .times(1)
+            .returning(|_| Ok(DeadLetterModel::mock_default()));
+        dead_letter_repository
+            .expect_delete_resolved()
+            .withf(|_, _, scopes, keys| scopes == ["Rust-Montreal", "Data-Quebec"] && keys == ["Data-Quebec"])
+            .times(1)
+            .returning(|_, _, _, _| Ok(()));
+
+        let adapters = AdapterRegistry::<dyn GroupAdapter>::new("group").register("meetup", Arc::new(adapter));
+        let group_sync = GroupSync::new(
+            adapters,
+            Arc::new(repository),
+            Arc::new(diff_repository),
+            Arc::new(registered_group_repository),
+            Arc::new(dead_letter_repository),
+            Arc::new(MockFakeFieldChangeRepository::new()),
+            1,
+        );
+        let context = SyncContext::new(Shutdown::manual(Duration::ZERO).1).with_run_id(Some(7));
+
+        assert_eq!(group_sync.sync(&context).await.unwrap().inserted, 1);
     }
 }
@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use tokio::{sync::watch, time::sleep};
+
+// Conventional exit code of a process killed by SIGINT
+const EXIT_INTERRUPTED: i32 = 130;
+
+/// Shutdown request shared by every job, set once SIGTERM or SIGINT is received.
+///
+/// Jobs stop between batches once it is requested, and in-flight runs get
+/// `timeout` to finish before they are aborted.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    receiver: watch::Receiver<bool>,
+    timeout: Duration,
+}
+
+impl Shutdown {
+    /// Listens for SIGTERM and SIGINT for the rest of the process.
+    pub fn listen(timeout: Duration) -> Self {
+        let (sender, receiver) = watch::channel(false);
+
+        tokio::spawn(async move {
+            wait_for_signal().await;
+            tracing::info!("Shutdown requested, finishing in-flight batches within {}s", timeout.as_secs());
+            let _ = sender.send(true);
+
+            wait_for_signal().await;
+            tracing::warn!("Shutdown requested again, exiting without waiting");
+            std::process::exit(EXIT_INTERRUPTED);
+        });
+
+        Self { receiver, timeout }
+    }
+
+    pub fn is_requested(&self) -> bool {
+        *self.receiver.borrow()
+    }
+
+    /// Resolves once shutdown is requested.
+    pub async fn requested(&self) {
+        let mut receiver = self.receiver.clone();
+
+        if receiver.wait_for(|requested| *requested).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+
+    /// Resolves once in-flight runs are out of time to finish.
+    pub async fn deadline(&self) {
+        self.requested().await;
+        sleep(self.timeout).await;
+    }
+}
+
+#[cfg(test)]
+impl Shutdown {
+    /// Shutdown requested through the returned sender instead of a signal.
+    pub fn manual(timeout: Duration) -> (watch::Sender<bool>, Self) {
+        let (sender, receiver) = watch::channel(false);
+        (sender, Self { receiver, timeout })
+    }
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match signal(SignalKind::terminate()) {
+        Ok(mut terminate) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        Err(err) => {
+            tracing::error!("Failed to listen for SIGTERM: {}", err);
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn it_should_resolve_deadline_after_timeout() {
+        let (sender, shutdown) = Shutdown::manual(Duration::from_millis(10));
+
+        assert!(!shutdown.is_requested());
+        sender.send(true).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown.deadline())
+            .await
+            .unwrap();
+        assert!(shutdown.is_requested());
+    }
+}
@@ -0,0 +1,835 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use chrono::Utc;
+use tokio::time::sleep;
+use tracing::Instrument;
+
+use crate::domain::{
+    error::DomainError,
+    sync_lock::repository::{SyncLock, SyncLockRepository},
+    sync_run::{
+        model::{SyncReport, SyncRunCreateModel, SyncRunUpdateModel},
+        repository::SyncRunRepository,
+        resources::{create, find_last_succeeded, update_by_id},
+    },
+};
+
+use super::{lib::{Force, SyncContext, SyncJob}, schedule::Schedule, shutdown::Shutdown};
+
+// How often a waiting job tries again to take the lock of another instance
+const LOCK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Immediate retries of a failed run, before waiting for the next scheduled one.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Exponential backoff before retry number `attempt` (starting at 0), capped at `max_backoff`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt);
+        self.initial_backoff.saturating_mul(factor).min(self.max_backoff)
+    }
+}
+
+/// What a job does when another instance of the service is already running it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockMode {
+    Skip,
+    Wait,
+}
+
+impl FromStr for LockMode {
+    type Err = DomainError;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "skip" => Ok(LockMode::Skip),
+            "wait" => Ok(LockMode::Wait),
+            _ => Err(DomainError::BadRequest(format!("Invalid lock mode '{}', expected skip or wait", mode))),
+        }
+    }
+}
+
+/// Runs sync jobs, retrying failed runs and recording every attempt in `sync_run`.
+///
+/// A job holds its lock for the whole run, retries included, so it never runs
+/// on two instances at the same time.
+#[derive(Clone)]
+pub struct Supervisor {
+    sync_run_repository: Arc<dyn SyncRunRepository>,
+    sync_lock_repository: Arc<dyn SyncLockRepository>,
+    retry_policy: RetryPolicy,
+    catch_up_grace: Duration,
+    lock_mode: LockMode,
+    lock_poll_interval: Duration,
+    shutdown: Shutdown,
+    force: Option<Force>,
+}
+
+impl Supervisor {
+    pub fn new(
+        sync_run_repository: Arc<dyn SyncRunRepository>,
+        sync_lock_repository: Arc<dyn SyncLockRepository>,
+        retry_policy: RetryPolicy,
+        catch_up_grace: Duration,
+        lock_mode: LockMode,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            sync_run_repository,
+            sync_lock_repository,
+            retry_policy,
+            catch_up_grace,
+            lock_mode,
+            lock_poll_interval: LOCK_POLL_INTERVAL,
+            shutdown,
+            force: None,
+        }
+    }
+
+    /// Forces the runs started by this supervisor.
+    pub fn with_force(mut self, force: Option<Force>) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Runs `job` on `schedule` until shutdown or until the schedule has no
+    /// upcoming run.
+    ///
+    /// A run missed while the service was down is caught up right away. A
+    /// failed run (error or panic) is logged and retried according to the
+    /// retry policy, then the job simply waits for its next tick, so other
+    /// jobs are never affected.
+    pub async fn supervise(&self, job: Arc<dyn SyncJob>, schedule: Schedule) {
+        let mut consecutive_failures = 0;
+
+        if self.is_overdue(job.name(), &schedule).await {
+            tracing::info!("{} missed a scheduled run, catching up", job.name());
+            if let Err(err) = self.run_with_retries(job.clone()).await {
+                consecutive_failures += 1;
+                tracing::error!("{} catch-up sync failed, retrying on next schedule: {}", job.name(), err);
+            }
+        }
+
+        loop {
+            let delay = match schedule.duration_until_next() {
+                Ok(delay) => delay,
+                Err(err) => {
+                    tracing::error!("{} is no longer scheduled: {}", job.name(), err);
+                    return;
+                }
+            };
+
+            // Wait until the next scheduled run
+            tokio::select! {
+                _ = sleep(delay) => {}
+                _ = self.shutdown.requested() => return,
+            }
+
+            match self.run_with_retries(job.clone()).await {
+                Ok(_) => consecutive_failures = 0,
+                Err(err) => {
+                    consecutive_failures += 1;
+                    tracing::error!(
+                        "{} sync failed ({} consecutive failed runs), retrying on next schedule: {}",
+                        job.name(),
+                        consecutive_failures,
+                        err
+                    );
+                }
+            }
+
+            if self.shutdown.is_requested() {
+                return;
+            }
+        }
+    }
+
+    /// Runs `job` once, retrying immediately with backoff on failure.
+    ///
+    /// Returns `None` when the run is skipped because shutdown was requested
+    /// or another instance holds the job's lock.
+    pub async fn run_with_retries(&self, job: Arc<dyn SyncJob>) -> Result<Option<SyncReport>, DomainError> {
+        let span = tracing::info_span!("job", job = job.name());
+        self.run_locked(job).instrument(span).await
+    }
+
+    async fn run_locked(&self, job: Arc<dyn SyncJob>) -> Result<Option<SyncReport>, DomainError> {
+        if self.shutdown.is_requested() {
+            return Ok(None);
+        }
+
+        let mut lock = match self.lock(job.name()).await? {
+            Some(lock) => lock,
+            None if self.shutdown.is_requested() => return Ok(None),
+            None => {
+                tracing::info!("{} sync skipped, another instance is running it", job.name());
+                return Ok(None);
+            }
+        };
+
+        let result = self.run_retrying(job.clone()).await;
+
+        if let Err(err) = lock.release().await {
+            tracing::error!("Failed to release {} sync lock: {}", job.name(), err);
+        }
+
+        result.map(Some)
+    }
+
+    // Takes the job's lock. A waiting job polls for it rather than blocking on
+    // the database, so that shutdown can stop the wait.
+    async fn lock(&self, job: &str) -> Result<Option<Box<dyn SyncLock>>, DomainError> {
+        loop {
+            let lock = self.sync_lock_repository.try_acquire(job).await?;
+            if lock.is_some() || self.lock_mode == LockMode::Skip {
+                return Ok(lock);
+            }
+
+            tracing::debug!("{} sync waiting for another instance to release its lock", job);
+            tokio::select! {
+                _ = sleep(self.lock_poll_interval) => {}
+                _ = self.shutdown.requested() => return Ok(None),
+            }
+        }
+    }
+
+    async fn run_retrying(&self, job: Arc<dyn SyncJob>) -> Result<SyncReport, DomainError> {
+        let mut attempt = 0;
+
+        loop {
+            match self.run_recorded(job.clone()).await {
+                Ok(report) => return Ok(report),
+                Err(err) if attempt < self.retry_policy.max_retries && !self.shutdown.is_requested() => {
+                    let backoff = self.retry_policy.backoff(attempt);
+                    tracing::warn!(
+                        "{} sync attempt {} failed, retrying in {}s: {}",
+                        job.name(),
+                        attempt + 1,
+                        backoff.as_secs(),
+                        err
+                    );
+                    tokio::select! {
+                        _ = sleep(backoff) => {}
+                        _ = self.shutdown.requested() => return Err(err),
+                    }
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Whether a run of `job` was scheduled since its last successful run and
+    /// within the catch-up grace window. A job that never succeeded has no
+    /// missed run: it waits for its first scheduled tick.
+    async fn is_overdue(&self, job: &str, schedule: &Schedule) -> bool {
+        if self.catch_up_grace.is_zero() {
+            return false;
+        }
+
+        match find_last_succeeded::execute(self.sync_run_repository.clone(), job.to_string()).await {
+            Ok(Some(sync_run)) => schedule.missed_since(&sync_run.started_at, &Utc::now(), self.catch_up_grace),
+            Ok(None) => false,
+            Err(err) => {
+                tracing::error!("Failed to find last {} sync, skipping catch-up: {}", job, err);
+                false
+            }
+        }
+    }
+
+    // Runs the sync once, with a `sync_run` row opened at start and closed at finish.
+    // Failing to record the run is logged but never prevents the sync itself. A
+    // run still going when shutdown is requested is recorded as interrupted.
+    async fn run_recorded(&self, job: Arc<dyn SyncJob>) -> Result<SyncReport, DomainError> {
+        let name = job.name();
+
+        let sync_run = create::execute(
+            self.sync_run_repository.clone(),
+            SyncRunCreateModel::new(name.to_string()),
+        )
+        .await
+        .map_err(|err| tracing::error!("Failed to record start of {} sync: {}", name, err))
+        .ok();
+
+        let run_id = sync_run.as_ref().map(|sync_run| sync_run.sync_runid);
+        let span = tracing::info_span!("run", run_id);
+        let result = self.run_isolated(job, run_id).instrument(span).await;
+
+        if let Some(sync_run) = sync_run {
+            let sync_run_update_model = match (&result, self.shutdown.is_requested()) {
+                (Ok(report), false) => SyncRunUpdateModel::succeeded(*report),
+                (Err(err), false) => SyncRunUpdateModel::failed(err.to_string()),
+                (Ok(report), true) => SyncRunUpdateModel::interrupted(Some(*report), None),
+                (Err(err), true) => SyncRunUpdateModel::interrupted(None, Some(err.to_string())),
+            };
+
+            if let Err(err) = update_by_id::execute(
+                self.sync_run_repository.clone(),
+                sync_run.sync_runid,
+                sync_run_update_model,
+            )
+            .await
+            {
+                tracing::error!("Failed to record end of {} sync: {}", name, err);
+            }
+        }
+
+        result
+    }
+
+    // Runs the sync on its own task so a panic fails the run instead of the
+    // scheduler, and so it can be aborted once the shutdown deadline is reached
+    async fn run_isolated(&self, job: Arc<dyn SyncJob>, run_id: Option<i32>) -> Result<SyncReport, DomainError> {
+        let name = job.name();
+        let context = SyncContext::new(self.shutdown.clone())
+            .with_run_id(run_id)
+            .with_force(self.force.clone());
+
+        let mut handle = tokio::spawn(
+            async move { job.sync(&context).await }.instrument(tracing::Span::current()),
+        );
+
+        tokio::select! {
+            result = &mut handle => result
+                .map_err(|err| DomainError::InternalServerError(format!("{} sync panicked: {}", name, err)))?,
+            _ = self.shutdown.deadline() => {
+                handle.abort();
+                Err(DomainError::InternalServerError(format!("{} sync aborted at shutdown deadline", name)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+impl Supervisor {
+    fn with_lock_poll_interval(mut self, lock_poll_interval: Duration) -> Self {
+        self.lock_poll_interval = lock_poll_interval;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::{mock, predicate::eq, Sequence};
+
+    use chrono::DateTime;
+
+    use crate::domain::sync_run::model::{SyncRunModel, SyncRunStatus};
+
+    mock! {
+        pub FakeSyncJob { }
+
+        #[async_trait]
+        impl SyncJob for FakeSyncJob {
+            fn name(&self) -> &'static str;
+            async fn sync(&self, context: &SyncContext) -> Result<SyncReport, DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeSyncRunRepository { }
+
+        #[async_trait]
+        impl SyncRunRepository for FakeSyncRunRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+            async fn insert(&self,sync_run_create_model: &SyncRunCreateModel) -> Result<SyncRunModel, DomainError>;
+            async fn update_by_id(&self,id: &i32,sync_run_update_model: &SyncRunUpdateModel) -> Result<SyncRunModel, DomainError>;
+            async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeSyncLock { }
+
+        #[async_trait]
+        impl SyncLock for FakeSyncLock {
+            async fn release(&mut self) -> Result<(), DomainError>;
+        }
+    }
+
+    mock! {
+        pub FakeSyncLockRepository { }
+
+        #[async_trait]
+        impl SyncLockRepository for FakeSyncLockRepository {
+            async fn try_acquire(&self, job: &str) -> Result<Option<Box<dyn SyncLock>>, DomainError>;
+        }
+    }
+
+    fn released_lock() -> Box<dyn SyncLock> {
+        let mut lock = MockFakeSyncLock::new();
+        lock.expect_release().times(1).returning(|| Ok(()));
+        Box::new(lock)
+    }
+
+    // Supervisor of a single instance, that always gets the job's lock
+    fn supervisor(
+        sync_run_repository: MockFakeSyncRunRepository,
+        retry_policy: RetryPolicy,
+        catch_up_grace: Duration,
+    ) -> Supervisor {
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository
+            .expect_try_acquire()
+            .returning(|_| Ok(Some(released_lock())));
+
+        Supervisor::new(
+            Arc::new(sync_run_repository),
+            Arc::new(sync_lock_repository),
+            retry_policy,
+            catch_up_grace,
+            LockMode::Skip,
+            running(),
+        )
+    }
+
+    // Shutdown that is never requested
+    fn running() -> Shutdown {
+        Shutdown::manual(Duration::ZERO).1
+    }
+
+    fn no_backoff(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    // Expects `runs` recorded runs, leaving the expected update to the test
+    fn recording_repository(runs: usize) -> MockFakeSyncRunRepository {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_insert()
+            .times(runs)
+            .returning(|_| Ok(SyncRunModel::mock_default()));
+        sync_run_repository
+            .expect_find_by_id()
+            .times(runs)
+            .returning(|_| Ok(Some(SyncRunModel::mock_default())));
+
+        sync_run_repository
+    }
+
+    #[test]
+    fn it_should_double_backoff_up_to_max() {
+        let retry_policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(60),
+        };
+
+        assert_eq!(retry_policy.backoff(0), Duration::from_secs(10));
+        assert_eq!(retry_policy.backoff(1), Duration::from_secs(20));
+        assert_eq!(retry_policy.backoff(2), Duration::from_secs(40));
+        assert_eq!(retry_policy.backoff(3), Duration::from_secs(60));
+        assert_eq!(retry_policy.backoff(40), Duration::from_secs(60));
+    }
+
+    #[tokio::test]
+    async fn it_should_record_succeeded_run_with_report() {
+        let mut job = MockFakeSyncJob::new();
+        let report = SyncReport {
+            inserted: 2,
+            updated: 1,
+            unchanged: 5,
+            deleted: 0,
+        };
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(1).returning(move |_| Ok(report));
+
+        let mut sync_run_repository = recording_repository(1);
+        sync_run_repository
+            .expect_update_by_id()
+            .withf(move |_, update| update.status == SyncRunStatus::Succeeded && update.report == Some(report))
+            .times(1)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::ZERO);
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert_eq!(result.unwrap(), Some(report));
+    }
+
+    #[tokio::test]
+    async fn it_should_retry_until_sync_succeeds() {
+        let mut job = MockFakeSyncJob::new();
+        let mut sequence = Sequence::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync()
+            .times(2)
+            .in_sequence(&mut sequence)
+            .returning(|_| Err(DomainError::InternalServerError("Medium is down".to_string())));
+        job.expect_sync()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(SyncReport::default()));
+
+        let mut sync_run_repository = recording_repository(3);
+        sync_run_repository
+            .expect_update_by_id()
+            .times(3)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(3), Duration::ZERO);
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_retries_exhausted() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync()
+            .times(3)
+            .returning(|_| Err(DomainError::InternalServerError("Medium is down".to_string())));
+
+        let mut sync_run_repository = recording_repository(3);
+        sync_run_repository
+            .expect_update_by_id()
+            .withf(|_, update| update.status == SyncRunStatus::Failed && update.error.as_deref() == Some("Medium is down"))
+            .times(3)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(2), Duration::ZERO);
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        match result {
+            Err(DomainError::InternalServerError(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_sync_panicked() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync()
+            .times(1)
+            .returning(|_| panic!("Failed to extract link"));
+
+        let mut sync_run_repository = recording_repository(1);
+        sync_run_repository
+            .expect_update_by_id()
+            .withf(|_, update| update.status == SyncRunStatus::Failed)
+            .times(1)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::ZERO);
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        match result {
+            Err(DomainError::InternalServerError(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    fn last_succeeded_repository(started_at: Option<DateTime<Utc>>) -> MockFakeSyncRunRepository {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_find_last_succeeded()
+            .withf(|job| job == "articles")
+            .times(1)
+            .returning(move |_| {
+                Ok(started_at.map(|started_at| SyncRunModel {
+                    status: SyncRunStatus::Succeeded,
+                    started_at,
+                    ..SyncRunModel::mock_default()
+                }))
+            });
+
+        sync_run_repository
+    }
+
+    #[tokio::test]
+    async fn it_should_be_overdue_run_missed_within_grace() {
+        let schedule = Schedule::parse("@every 1h", chrono_tz::UTC).unwrap();
+        let sync_run_repository = last_succeeded_repository(Some(Utc::now() - chrono::Duration::hours(2)));
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::from_secs(86400));
+
+        assert!(supervisor.is_overdue("articles", &schedule).await);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_be_overdue_run_not_missed() {
+        let schedule = Schedule::parse("@every 1h", chrono_tz::UTC).unwrap();
+        let sync_run_repository = last_succeeded_repository(Some(Utc::now() - chrono::Duration::minutes(10)));
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::from_secs(86400));
+
+        assert!(!supervisor.is_overdue("articles", &schedule).await);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_be_overdue_never_succeeded() {
+        let schedule = Schedule::parse("@every 1h", chrono_tz::UTC).unwrap();
+        let sync_run_repository = last_succeeded_repository(None);
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::from_secs(86400));
+
+        assert!(!supervisor.is_overdue("articles", &schedule).await);
+    }
+
+    #[tokio::test]
+    async fn it_should_not_be_overdue_catch_up_disabled() {
+        let schedule = Schedule::parse("@every 1h", chrono_tz::UTC).unwrap();
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+        sync_run_repository.expect_find_last_succeeded().times(0);
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::ZERO);
+
+        assert!(!supervisor.is_overdue("articles", &schedule).await);
+    }
+
+    #[tokio::test]
+    async fn it_should_skip_sync_locked_by_another_instance() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(0);
+
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+        sync_run_repository.expect_insert().times(0);
+
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository
+            .expect_try_acquire()
+            .withf(|job| job == "articles")
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let supervisor = Supervisor::new(
+            Arc::new(sync_run_repository),
+            Arc::new(sync_lock_repository),
+            no_backoff(0),
+            Duration::ZERO,
+            LockMode::Skip,
+            running(),
+        );
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn it_should_wait_for_lock_held_by_another_instance() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(1).returning(|_| Ok(SyncReport::default()));
+
+        let mut sync_run_repository = recording_repository(1);
+        sync_run_repository
+            .expect_update_by_id()
+            .times(1)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        let mut sequence = Sequence::new();
+        sync_lock_repository
+            .expect_try_acquire()
+            .times(2)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(None));
+        sync_lock_repository
+            .expect_try_acquire()
+            .times(1)
+            .in_sequence(&mut sequence)
+            .returning(|_| Ok(Some(released_lock())));
+
+        let supervisor = Supervisor::new(
+            Arc::new(sync_run_repository),
+            Arc::new(sync_lock_repository),
+            no_backoff(0),
+            Duration::ZERO,
+            LockMode::Wait,
+            running(),
+        )
+        .with_lock_poll_interval(Duration::ZERO);
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert!(result.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn it_should_stop_waiting_for_lock_shutdown_requested() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(0);
+
+        let (sender, shutdown) = Shutdown::manual(Duration::ZERO);
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository.expect_try_acquire().times(1).returning(move |_| {
+            // Shutdown requested while the other instance still holds the lock
+            sender.send(true).unwrap();
+            Ok(None)
+        });
+
+        let supervisor = Supervisor::new(
+            Arc::new(MockFakeSyncRunRepository::new()),
+            Arc::new(sync_lock_repository),
+            no_backoff(0),
+            Duration::ZERO,
+            LockMode::Wait,
+            shutdown,
+        );
+        let result = tokio::time::timeout(Duration::from_secs(1), supervisor.run_with_retries(Arc::new(job))).await;
+
+        assert!(result.unwrap().unwrap().is_none());
+    }
+
+    #[test]
+    fn it_should_parse_lock_mode() {
+        assert_eq!("skip".parse::<LockMode>().unwrap(), LockMode::Skip);
+        assert_eq!("wait".parse::<LockMode>().unwrap(), LockMode::Wait);
+        assert!("block".parse::<LockMode>().is_err());
+    }
+
+    #[tokio::test]
+    async fn it_should_not_run_shutdown_requested() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(0);
+
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository.expect_try_acquire().times(0);
+
+        let (sender, shutdown) = Shutdown::manual(Duration::ZERO);
+        sender.send(true).unwrap();
+
+        let supervisor = Supervisor::new(
+            Arc::new(MockFakeSyncRunRepository::new()),
+            Arc::new(sync_lock_repository),
+            no_backoff(0),
+            Duration::ZERO,
+            LockMode::Skip,
+            shutdown,
+        );
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert!(result.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn it_should_record_interrupted_run_with_report() {
+        let (sender, shutdown) = Shutdown::manual(Duration::from_secs(60));
+        let mut job = MockFakeSyncJob::new();
+        let report = SyncReport {
+            inserted: 3,
+            updated: 0,
+            unchanged: 0,
+            deleted: 0,
+        };
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(1).returning(move |_| {
+            // Shutdown requested during the batch, the job stops after it
+            sender.send(true).unwrap();
+            Ok(report)
+        });
+
+        let mut sync_run_repository = recording_repository(1);
+        sync_run_repository
+            .expect_update_by_id()
+            .withf(move |_, update| update.status == SyncRunStatus::Interrupted && update.report == Some(report))
+            .times(1)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository
+            .expect_try_acquire()
+            .returning(|_| Ok(Some(released_lock())));
+
+        let supervisor = Supervisor::new(
+            Arc::new(sync_run_repository),
+            Arc::new(sync_lock_repository),
+            no_backoff(3),
+            Duration::ZERO,
+            LockMode::Skip,
+            shutdown,
+        );
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert_eq!(result.unwrap(), Some(report));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn it_should_abort_sync_at_shutdown_deadline() {
+        let (sender, shutdown) = Shutdown::manual(Duration::from_millis(10));
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(1).returning(move |_| {
+            sender.send(true).unwrap();
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(SyncReport::default())
+        });
+
+        let mut sync_run_repository = recording_repository(1);
+        sync_run_repository
+            .expect_update_by_id()
+            .withf(|_, update| update.status == SyncRunStatus::Interrupted && update.error.is_some())
+            .times(1)
+            .returning(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let mut sync_lock_repository = MockFakeSyncLockRepository::new();
+        sync_lock_repository
+            .expect_try_acquire()
+            .returning(|_| Ok(Some(released_lock())));
+
+        let supervisor = Supervisor::new(
+            Arc::new(sync_run_repository),
+            Arc::new(sync_lock_repository),
+            no_backoff(3),
+            Duration::ZERO,
+            LockMode::Skip,
+            shutdown,
+        );
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        match result {
+            Err(DomainError::InternalServerError(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_sync_when_run_cannot_be_recorded() {
+        let mut job = MockFakeSyncJob::new();
+
+        job.expect_name().return_const("articles");
+        job.expect_sync().times(1).returning(|_| Ok(SyncReport::default()));
+
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+        sync_run_repository
+            .expect_insert()
+            .with(eq(SyncRunCreateModel::new("articles".to_string())))
+            .times(1)
+            .returning(|_| Err(DomainError::InternalServerError("Sync database is down".to_string())));
+        sync_run_repository.expect_update_by_id().times(0);
+
+        let supervisor = supervisor(sync_run_repository, no_backoff(0), Duration::ZERO);
+        let result = supervisor.run_with_retries(Arc::new(job)).await;
+
+        assert!(result.is_ok());
+    }
+}
@@ -1,9 +1,16 @@
 use async_trait::async_trait;
-use crate::domain::error::DomainError;
+use crate::domain::{dead_letter::model::Fetched, error::DomainError, registered_author::model::RegisteredAuthorModel};
 
 use super::model::{ ArticleCreateModel};
 
 #[async_trait]
-pub trait ArticleAdapter {
-    async fn fetch(&self, author: String) -> Result<Vec<ArticleCreateModel>, DomainError>;
+pub trait ArticleAdapter: Send + Sync {
+    async fn fetch(&self, author: &RegisteredAuthorModel) -> Result<Fetched<ArticleCreateModel>, DomainError>;
+
+    /// Whether a fetch returns every article of the author, so any article it
+    /// misses is gone from the source, rather than only the latest ones, of
+    /// which only those published since the oldest returned can be gone.
+    fn lists_all(&self) -> bool {
+        false
+    }
 }
@@ -1,5 +1,10 @@
 use chrono::{DateTime, Utc};
 
+use crate::domain::{checksum::Checksum, field_change::model::{Diffable, FieldChange, FieldChanges}};
+
+// Bump when the mapping of fetched articles changes, to rewrite them all
+const CHECKSUM_VERSION: u32 = 3;
+
 
 pub trait Processable {
     fn get_checksum(&self) -> String;
@@ -29,7 +34,8 @@ pub struct ArticleCreateModel {
     pub highres_link: Option<String>,
     pub photo_link: Option<String>,
     pub thumb_link: Option<String>,
-    pub last_update: String,
+    pub tags: Vec<String>,
+    pub edited_at: Option<DateTime<Utc>>,
 }
 impl ArticleCreateModel {
     pub fn new(
@@ -44,7 +50,6 @@ impl ArticleCreateModel {
         highres_link: Option<String>,
         photo_link: Option<String>,
         thumb_link: Option<String>,
-        last_update: String,
     ) -> Self {
         Self {
             name,
@@ -58,10 +63,24 @@ impl ArticleCreateModel {
             highres_link,
             photo_link,
             thumb_link,
-            last_update,
+            tags: Vec::new(),
+            edited_at: None,
         }
     }
-    
+
+    /// Tags of the article at its source, kept in the sync database.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Last edit at the source, for the sources that tell, kept in the sync
+    /// database.
+    pub fn with_edited_at(mut self, edited_at: Option<DateTime<Utc>>) -> Self {
+        self.edited_at = edited_at;
+        self
+    }
+
     pub fn to_update(&self) -> ArticleUpdateModel {
         ArticleUpdateModel::new(
             self.extid.clone(),
@@ -80,7 +99,20 @@ impl ArticleCreateModel {
 
 impl Processable for ArticleCreateModel {
     fn get_checksum(&self) -> String {
-        self.last_update.clone()
+        Checksum::new(CHECKSUM_VERSION)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("time_m", &self.time_m)
+            .field("source", &self.source)
+            .field("link", &self.link)
+            .field("author", &self.author)
+            .field("publish_at", &self.publish_at)
+            .field("highres_link", &self.highres_link)
+            .field("photo_link", &self.photo_link)
+            .field("thumb_link", &self.thumb_link)
+            .field("tags", &self.tags)
+            .field("edited_at", &self.edited_at)
+            .finish()
     }
 }
 
@@ -109,7 +141,8 @@ impl ArticleCreateModel {
             highres_link: Some("The img".to_string()),
             photo_link: Some("The img".to_string()),
             thumb_link: Some("The img".to_string()),
-            last_update: "test".to_string(),
+            tags: Vec::new(),
+            edited_at: None,
         }
     }
 }
@@ -154,6 +187,23 @@ impl ArticleUpdateModel {
         }
     }
 }
+impl Diffable<ArticleModel> for ArticleUpdateModel {
+    fn changed_fields(&self, current: Option<&ArticleModel>) -> Vec<FieldChange> {
+        let mut changes = FieldChanges::new();
+        changes
+            .compare("name", current.map(|current| &current.name), &self.name)
+            .compare("description", current.map(|current| &current.description), &self.description)
+            .compare("time_m", current.map(|current| &current.time_m), &self.time_m)
+            .compare("link", current.map(|current| &current.link), &self.link)
+            .compare("author", current.map(|current| &current.author), &self.author)
+            .compare("publish_at", current.map(|current| &current.publish_at), &self.publish_at)
+            .compare("highres_link", current.map(|current| &current.highres_link), &self.highres_link)
+            .compare("photo_link", current.map(|current| &current.photo_link), &self.photo_link)
+            .compare("thumb_link", current.map(|current| &current.thumb_link), &self.thumb_link);
+        changes.into_vec()
+    }
+}
+
 #[cfg(test)]
 impl ArticleUpdateModel {
     pub fn mock_default() -> Self {
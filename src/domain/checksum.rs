@@ -0,0 +1,77 @@
+use sha2::{Digest, Sha256};
+
+use crate::domain::field_change::model::FieldValue;
+
+// Separates a field name from its value, and one field from the next
+const UNIT_SEPARATOR: u8 = 0x1f;
+const RECORD_SEPARATOR: u8 = 0x1e;
+
+/// SHA-256 over the mapped fields of an item, in the order they are added,
+/// so an item is rewritten whenever a field we store changes, whatever the
+/// upstream timestamps say.
+///
+/// The version of the entity mapping is part of the checksum: bumping it
+/// after changing a transformation (image sizes, HTML stripping, ...)
+/// rewrites every item of the entity on the next run.
+pub struct Checksum {
+    version: u32,
+    hasher: Sha256,
+}
+
+impl Checksum {
+    pub fn new(version: u32) -> Self {
+        Self {
+            version,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Adds a field by its text form, an unset field hashing differently
+    /// from an empty one.
+    pub fn field<V: FieldValue>(mut self, name: &str, value: &V) -> Self {
+        self.hasher.update(name.as_bytes());
+        self.hasher.update([UNIT_SEPARATOR]);
+        match value.to_field_value() {
+            Some(value) => {
+                self.hasher.update([b'+']);
+                self.hasher.update(value.as_bytes());
+            }
+            None => self.hasher.update([b'-']),
+        }
+        self.hasher.update([RECORD_SEPARATOR]);
+        self
+    }
+
+    /// `v<version>:<hex digest>`
+    pub fn finish(self) -> String {
+        format!("v{}:{:x}", self.version, self.hasher.finalize())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_be_stable_and_versioned() {
+        let checksum = Checksum::new(1).field("name", &"article".to_string()).finish();
+
+        assert!(checksum.starts_with("v1:"));
+        assert_eq!(checksum.len(), 3 + 64);
+        assert_eq!(checksum, Checksum::new(1).field("name", &"article".to_string()).finish());
+        assert_ne!(checksum, Checksum::new(2).field("name", &"article".to_string()).finish());
+    }
+
+    #[test]
+    fn it_should_change_with_any_field() {
+        let checksum = |description: Option<String>, count: i32| {
+            Checksum::new(1)
+                .field("description", &description)
+                .field("count", &count)
+                .finish()
+        };
+
+        assert_ne!(checksum(None, 1), checksum(Some(String::new()), 1));
+        assert_ne!(checksum(None, 1), checksum(None, 2));
+    }
+}
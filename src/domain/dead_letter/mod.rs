@@ -0,0 +1,3 @@
+pub mod model;
+pub mod repository;
+pub mod resources;
\ No newline at end of file
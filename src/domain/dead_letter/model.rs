@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
+
+#[cfg(test)]
+use crate::api::utils::random_number;
+#[cfg(test)]
+use crate::api::utils::random_string;
+
+// Longest part of an upstream payload kept with a failure
+const PAYLOAD_EXCERPT_CHARS: usize = 2000;
+
+/// An item an adapter failed to fetch or map, or a whole author or group
+/// when it failed before any item was read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FetchFailure {
+    pub source: String,
+    /// Author or group the item was fetched for.
+    pub scope: String,
+    /// Extid of the item, or the scope when the whole scope failed.
+    pub key: String,
+    pub payload: Option<String>,
+    pub error: String,
+}
+impl FetchFailure {
+    pub fn new(source: &str, scope: &str, key: &str, error: impl Display) -> Self {
+        Self {
+            source: source.to_string(),
+            scope: scope.to_string(),
+            key: key.to_string(),
+            payload: None,
+            error: error.to_string(),
+        }
+    }
+
+    /// Failure of a whole author or group.
+    pub fn scope(source: &str, scope: &str, error: impl Display) -> Self {
+        Self::new(source, scope, scope, error)
+    }
+
+    /// Keeps the start of the upstream payload that failed.
+    pub fn with_payload(mut self, payload: &str) -> Self {
+        self.payload = Some(payload.chars().take(PAYLOAD_EXCERPT_CHARS).collect());
+        self
+    }
+}
+
+/// Items an adapter fetched, and the ones it could not.
+#[derive(Debug)]
+pub struct Fetched<T> {
+    pub items: Vec<T>,
+    pub failures: Vec<FetchFailure>,
+}
+impl<T> Fetched<T> {
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            failures: Vec::new(),
+        }
+    }
+
+    pub fn extend(&mut self, other: Fetched<T>) {
+        self.items.extend(other.items);
+        self.failures.extend(other.failures);
+    }
+
+    /// Whether every item of `scope` was fetched, nothing failing for it.
+    pub fn is_complete(&self, scope: &str) -> bool {
+        !self.failures.iter().any(|failure| failure.scope == scope)
+    }
+}
+
+impl<T> From<Vec<T>> for Fetched<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self {
+            items,
+            failures: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeadLetterCreateModel {
+    pub job: String,
+    pub source: String,
+    pub scope: String,
+    pub key: String,
+    pub payload: Option<String>,
+    pub error: String,
+}
+impl DeadLetterCreateModel {
+    pub fn new(job: &str, failure: FetchFailure) -> Self {
+        Self {
+            job: job.to_string(),
+            source: failure.source,
+            scope: failure.scope,
+            key: failure.key,
+            payload: failure.payload,
+            error: failure.error,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadLetterModel {
+    pub sync_dead_letterid: i32,
+    pub job: String,
+    pub source: String,
+    pub scope: String,
+    pub key: String,
+    pub payload: Option<String>,
+    pub error: String,
+    pub attempts: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+#[cfg(test)]
+impl DeadLetterModel {
+    pub fn mock_default() -> Self {
+        Self {
+            sync_dead_letterid: random_number(),
+            job: "articles".to_string(),
+            source: "medium".to_string(),
+            scope: "kozyrkov".to_string(),
+            key: random_string(10),
+            payload: Some("<item/>".to_string()),
+            error: "Missing image".to_string(),
+            attempts: 1,
+            created_at: DateTime::default(),
+            updated_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_keep_payload_excerpt() {
+        let failure = FetchFailure::scope("medium", "kozyrkov", "Invalid feed").with_payload(&"é".repeat(3000));
+
+        assert_eq!(failure.key, "kozyrkov");
+        assert_eq!(failure.payload.unwrap().chars().count(), PAYLOAD_EXCERPT_CHARS);
+    }
+
+    #[test]
+    fn it_should_not_be_complete_scope_with_failure() {
+        let mut fetched: Fetched<String> = vec!["article".to_string()].into();
+        fetched.failures.push(FetchFailure::new("devto", "datanorth", "1598213", "Not published"));
+
+        assert!(!fetched.is_complete("datanorth"));
+        assert!(fetched.is_complete("kozyrkov"));
+    }
+}
@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+
+use super::model::{DeadLetterCreateModel, DeadLetterModel};
+
+#[async_trait]
+pub trait DeadLetterRepository: Send + Sync {
+    async fn find(
+        &self,
+        job: &Option<String>,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+    async fn find_by_id(&self, id: &i32) -> Result<Option<DeadLetterModel>, DomainError>;
+    /// Records a failure, counting one more attempt when the item failed before.
+    async fn upsert(
+        &self,
+        dead_letter_create_model: &DeadLetterCreateModel,
+    ) -> Result<DeadLetterModel, DomainError>;
+    /// Deletes the dead letters of `scopes` but `keys`, the ones that failed again.
+    async fn delete_resolved(
+        &self,
+        job: &str,
+        source: &str,
+        scopes: &[String],
+        keys: &[String],
+    ) -> Result<(), DomainError>;
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    dead_letter::{model::DeadLetterModel, repository::DeadLetterRepository},
+    error::DomainError,
+};
+
+pub async fn execute(
+    dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    job: Option<String>,
+    page: u32,
+    page_size: u32,
+) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError> {
+    dead_letter_repository.find(&job, &page, &page_size).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::dead_letter::model::DeadLetterCreateModel;
+
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<DeadLetterModel>, DomainError>;
+            async fn upsert(&self,dead_letter_create_model: &DeadLetterCreateModel) -> Result<DeadLetterModel, DomainError>;
+            async fn delete_resolved(&self,job: &str,source: &str,scopes: &[String],keys: &[String]) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_dead_letters_finded() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_find()
+            .withf(|job, _, _| job.as_deref() == Some("articles"))
+            .return_once(|_, _, _| Ok(Some((vec![DeadLetterModel::mock_default()], 1))));
+
+        let (dead_letters, count) = execute(Arc::new(dead_letter_repository), Some("articles".to_string()), 1, 12)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(count, 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_finded() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_find()
+            .return_once(|_, _, _| Ok(None));
+
+        let response = execute(Arc::new(dead_letter_repository), None, 1, 12).await.unwrap();
+
+        assert!(response.is_none());
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    dead_letter::{model::DeadLetterModel, repository::DeadLetterRepository},
+    error::DomainError,
+};
+
+pub async fn execute(
+    dead_letter_repository: Arc<dyn DeadLetterRepository>,
+    id: i32,
+) -> Result<DeadLetterModel, DomainError> {
+    dead_letter_repository
+        .find_by_id(&id)
+        .await?
+        .ok_or_else(|| DomainError::NotFound(format!("Dead letter {} not found", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::{mock, predicate::eq};
+
+    use crate::domain::dead_letter::model::DeadLetterCreateModel;
+
+    mock! {
+        pub FakeDeadLetterRepository { }
+
+        #[async_trait]
+        impl DeadLetterRepository for FakeDeadLetterRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<DeadLetterModel>, DomainError>;
+            async fn upsert(&self,dead_letter_create_model: &DeadLetterCreateModel) -> Result<DeadLetterModel, DomainError>;
+            async fn delete_resolved(&self,job: &str,source: &str,scopes: &[String],keys: &[String]) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_dead_letter_finded() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_find_by_id()
+            .with(eq(7))
+            .return_once(|_| Ok(Some(DeadLetterModel::mock_default())));
+
+        assert!(execute(Arc::new(dead_letter_repository), 7).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_not_found() {
+        let mut dead_letter_repository = MockFakeDeadLetterRepository::new();
+        dead_letter_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(None));
+
+        let result = execute(Arc::new(dead_letter_repository), 7).await;
+
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+    }
+}
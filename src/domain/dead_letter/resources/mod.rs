@@ -0,0 +1,2 @@
+pub mod find;
+pub mod find_by_id;
\ No newline at end of file
@@ -2,6 +2,8 @@
 use crate::api::utils::{
     random_string,
 };
+use chrono::{DateTime, Utc};
+
 use crate::domain::article::model::{Guidable, Processable};
 
 
@@ -9,6 +11,9 @@ use crate::domain::article::model::{Guidable, Processable};
 pub struct DiffArticleModel {
     pub key: String,
     pub value: String,
+    pub tags: Vec<String>,
+    pub edited_at: Option<DateTime<Utc>>,
+    pub publish_at: Option<DateTime<Utc>>,
 }
 impl DiffArticleModel {
     pub fn  new(
@@ -18,8 +23,29 @@ impl DiffArticleModel {
         Self {
             key,
             value,
+            tags: Vec::new(),
+            edited_at: None,
+            publish_at: None,
         }
     }
+
+    /// Tags of the article at its source.
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    /// Last edit of the article at its source.
+    pub fn with_edited_at(mut self, edited_at: Option<DateTime<Utc>>) -> Self {
+        self.edited_at = edited_at;
+        self
+    }
+
+    /// Publish date, telling whether a feed still lists the article.
+    pub fn with_publish_at(mut self, publish_at: DateTime<Utc>) -> Self {
+        self.publish_at = Some(publish_at);
+        self
+    }
 }
 
 impl Guidable for DiffArticleModel {
@@ -43,6 +69,9 @@ impl DiffArticleModel {
         Self {
             key: random_string(10),
             value: "article".to_string(),
+            tags: Vec::new(),
+            edited_at: None,
+            publish_at: None,
         }
     }
 }
\ No newline at end of file
@@ -2,6 +2,8 @@
 use crate::api::utils::{
     random_string,
 };
+use chrono::{DateTime, Utc};
+
 use crate::domain::article::model::{Guidable, Processable};
 
 
@@ -9,6 +11,7 @@ use crate::domain::article::model::{Guidable, Processable};
 pub struct DiffEventModel {
     pub key: String,
     pub value: String,
+    pub time: Option<DateTime<Utc>>,
 }
 impl DiffEventModel {
     pub fn new(
@@ -18,8 +21,15 @@ impl DiffEventModel {
         Self {
             key,
             value,
+            time: None,
         }
     }
+
+    /// Start time, telling whether Meetup still lists the event as upcoming.
+    pub fn with_time(mut self, time: DateTime<Utc>) -> Self {
+        self.time = Some(time);
+        self
+    }
 }
 
 impl Guidable for DiffEventModel {
@@ -43,6 +53,7 @@ impl DiffEventModel {
         Self {
             key: random_string(10),
             value: "event".to_string(),
+            time: None,
         }
     }
 }
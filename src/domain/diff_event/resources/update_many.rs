@@ -19,6 +19,7 @@ mod tests {
     use super::*;
 
     use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
     use mockall::mock;
     
     use crate::{api::lib::{BatchOperations, DiffOperations}};
@@ -47,6 +48,21 @@ mod tests {
                 // Mock implementation
                 Ok(Vec::new())
             }
+
+            async fn touch_many(&self, _extids: Vec<String>, _source: &str, _scope: &str, _run_id: i32) -> Result<(), DomainError> {
+                // Mock implementation
+                Ok(())
+            }
+
+            async fn mark_unseen(&self, _run_id: i32, _source: &str, _scopes: Vec<String>, _since: Option<DateTime<Utc>>, _missed_runs: i32) -> Result<Vec<String>, DomainError> {
+                // Mock implementation
+                Ok(Vec::new())
+            }
+
+            async fn delete_by_extids(&self, _extids: Vec<String>) -> Result<(), DomainError> {
+                // Mock implementation
+                Ok(())
+            }
         }
     }
 
@@ -1,9 +1,9 @@
 use async_trait::async_trait;
-use crate::domain::{error::DomainError, group::model::GroupModel};
+use crate::domain::{dead_letter::model::Fetched, error::DomainError, group::model::GroupModel};
 
 use super::model::{ EventCreateModel};
 
 #[async_trait]
-pub trait EventAdapter {
-    async fn fetch(&self, group_models: Vec<GroupModel>) -> Result<Vec<EventCreateModel>, DomainError>;
+pub trait EventAdapter: Send + Sync {
+    async fn fetch(&self, group_models: Vec<GroupModel>) -> Result<Fetched<EventCreateModel>, DomainError>;
 }
\ No newline at end of file
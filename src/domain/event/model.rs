@@ -6,6 +6,11 @@ use crate::api::utils::random_number;
 use crate::api::utils::random_string;
 use crate::domain::article::model::Guidable;
 use crate::domain::article::model::Processable;
+use crate::domain::checksum::Checksum;
+use crate::domain::field_change::model::{Diffable, FieldChange, FieldChanges};
+
+// Bump when the mapping of fetched events changes, to rewrite them all
+const CHECKSUM_VERSION: u32 = 2;
 
 #[derive(Debug, Clone)]
 pub struct EventCreateModel {
@@ -26,7 +31,7 @@ pub struct EventCreateModel {
     pub highres_link: Option<String>,
     pub photo_link: Option<String>,
     pub thumb_link: Option<String>,
-    pub last_update: String,
+    pub group_slug: String,
 }
 impl EventCreateModel {
     pub fn new(
@@ -47,7 +52,6 @@ impl EventCreateModel {
         highres_link: Option<String>,
         photo_link: Option<String>,
         thumb_link: Option<String>,
-        last_update: String,
     ) -> Self {
         Self {
             name,
@@ -67,10 +71,16 @@ impl EventCreateModel {
             highres_link, 
             photo_link,
             thumb_link,
-            last_update,
+            group_slug: String::new(),
         }
     }
 
+    /// Slug of the group the event was fetched for, which scopes its sync.
+    pub fn with_group_slug(mut self, group_slug: &str) -> Self {
+        self.group_slug = group_slug.to_string();
+        self
+    }
+
     pub fn to_update(&self) -> EventUpdateModel {
         EventUpdateModel::new( 
             self.extid.clone(),
@@ -96,7 +106,24 @@ impl EventCreateModel {
 
 impl Processable for EventCreateModel {
     fn get_checksum(&self) -> String {
-        self.last_update.clone()
+        Checksum::new(CHECKSUM_VERSION)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("location", &self.location)
+            .field("groupid", &self.groupid)
+            .field("in_person", &self.in_person)
+            .field("is_online", &self.is_online)
+            .field("time", &self.time)
+            .field("duration", &self.duration)
+            .field("link", &self.link)
+            .field("waitlist_count", &self.waitlist_count)
+            .field("fee", &self.fee)
+            .field("yes_rsvp_count", &self.yes_rsvp_count)
+            .field("rsvp_limit", &self.rsvp_limit)
+            .field("highres_link", &self.highres_link)
+            .field("photo_link", &self.photo_link)
+            .field("thumb_link", &self.thumb_link)
+            .finish()
     }
 }
 
@@ -117,7 +144,6 @@ impl EventCreateModel {
             name: "event".to_string(),
             description: "The Big Event".to_string(),
             location: "boulvar".to_string(),
-            last_update: "boulvar".to_string(),
             groupid: random_number(),
             extid: random_string(10),
             in_person: true,
@@ -132,6 +158,7 @@ impl EventCreateModel {
             highres_link: Some("".to_string()),
             photo_link: Some("".to_string()),
             thumb_link: Some("".to_string()),
+            group_slug: random_string(10),
         }
     }
 }
@@ -197,6 +224,30 @@ impl EventUpdateModel {
         }
     }
 }
+impl Diffable<EventModel> for EventUpdateModel {
+    fn changed_fields(&self, current: Option<&EventModel>) -> Vec<FieldChange> {
+        let mut changes = FieldChanges::new();
+        changes
+            .compare("name", current.map(|current| &current.name), &self.name)
+            .compare("description", current.map(|current| &current.description), &self.description)
+            .compare("location", current.map(|current| &current.location), &self.location)
+            .compare("groupid", current.map(|current| &current.groupid), &self.groupid)
+            .compare("in_person", current.map(|current| &current.in_person), &self.in_person)
+            .compare("is_online", current.map(|current| &current.is_online), &self.is_online)
+            .compare("time", current.map(|current| &current.time), &self.time)
+            .compare("duration", current.map(|current| &current.duration), &self.duration)
+            .compare("link", current.map(|current| &current.link), &self.link)
+            .compare("waitlist_count", current.map(|current| &current.waitlist_count), &self.waitlist_count)
+            .compare("fee", current.map(|current| &current.fee), &self.fee)
+            .compare("yes_rsvp_count", current.map(|current| &current.yes_rsvp_count), &self.yes_rsvp_count)
+            .compare("rsvp_limit", current.map(|current| &current.rsvp_limit), &self.rsvp_limit)
+            .compare("highres_link", current.map(|current| &current.highres_link), &self.highres_link)
+            .compare("photo_link", current.map(|current| &current.photo_link), &self.photo_link)
+            .compare("thumb_link", current.map(|current| &current.thumb_link), &self.thumb_link);
+        changes.into_vec()
+    }
+}
+
 #[cfg(test)]
 impl EventUpdateModel {
     pub fn mock_default() -> Self {
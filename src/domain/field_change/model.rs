@@ -0,0 +1,189 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+#[cfg(test)]
+use crate::api::utils::random_number;
+
+/// Value of a single field before and after a sync, `None` when unset.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// Fields of a synced item that differ from the stored `M`.
+pub trait Diffable<M> {
+    /// Every field differing from `current`, or every set field of a new item
+    /// when there is no `current`.
+    fn changed_fields(&self, current: Option<&M>) -> Vec<FieldChange>;
+}
+
+/// Text form of a field value, as compared and reported.
+pub trait FieldValue {
+    fn to_field_value(&self) -> Option<String>;
+}
+
+impl FieldValue for String {
+    fn to_field_value(&self) -> Option<String> {
+        Some(self.clone())
+    }
+}
+
+impl FieldValue for i32 {
+    fn to_field_value(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl FieldValue for bool {
+    fn to_field_value(&self) -> Option<String> {
+        Some(self.to_string())
+    }
+}
+
+impl FieldValue for DateTime<Utc> {
+    fn to_field_value(&self) -> Option<String> {
+        Some(self.to_rfc3339())
+    }
+}
+
+// Comma separated, an empty list being unset
+impl FieldValue for Vec<String> {
+    fn to_field_value(&self) -> Option<String> {
+        (!self.is_empty()).then(|| self.join(", "))
+    }
+}
+
+impl<T: FieldValue> FieldValue for Option<T> {
+    fn to_field_value(&self) -> Option<String> {
+        self.as_ref().and_then(FieldValue::to_field_value)
+    }
+}
+
+/// A field change written by a sync, appended to the change history.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChangeCreateModel {
+    pub entity: String,
+    pub extid: String,
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub run_id: Option<i32>,
+}
+impl FieldChangeCreateModel {
+    pub fn new(entity: &str, extid: &str, change: FieldChange, run_id: Option<i32>) -> Self {
+        Self {
+            entity: entity.to_string(),
+            extid: extid.to_string(),
+            field: change.field,
+            before: change.before,
+            after: change.after,
+            run_id,
+        }
+    }
+}
+
+/// A recorded change of one item's field, found by the item entity and extid.
+#[derive(Debug, Clone)]
+pub struct FieldChangeModel {
+    pub sync_field_changeid: i32,
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub run_id: Option<i32>,
+    pub changed_at: DateTime<Utc>,
+}
+#[cfg(test)]
+impl FieldChangeModel {
+    pub fn mock_default() -> Self {
+        Self {
+            sync_field_changeid: random_number(),
+            field: "venue".to_string(),
+            before: Some("Shopify".to_string()),
+            after: Some("Mozilla".to_string()),
+            run_id: Some(random_number()),
+            changed_at: DateTime::default(),
+        }
+    }
+}
+
+/// Collects field changes, comparing values by their text form.
+#[derive(Debug, Default)]
+pub struct FieldChanges(Vec<FieldChange>);
+
+impl FieldChanges {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `field` if `after` differs from `before`, a missing `before`
+    /// standing for a new item.
+    pub fn compare<B: FieldValue, A: FieldValue>(&mut self, field: &str, before: Option<&B>, after: &A) -> &mut Self {
+        let before = before.and_then(FieldValue::to_field_value);
+        let after = after.to_field_value();
+
+        if before != after {
+            self.0.push(FieldChange {
+                field: field.to_string(),
+                before,
+                after,
+            });
+        }
+
+        self
+    }
+
+    pub fn into_vec(self) -> Vec<FieldChange> {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_record_changed_fields_only() {
+        let mut changes = FieldChanges::new();
+
+        changes
+            .compare("name", Some(&"Rust".to_string()), &"Rust Toronto".to_string())
+            .compare("members", Some(&10), &10)
+            .compare("cityid", Some(&3), &None::<i32>);
+
+        assert_eq!(
+            changes.into_vec(),
+            vec![
+                FieldChange {
+                    field: "name".to_string(),
+                    before: Some("Rust".to_string()),
+                    after: Some("Rust Toronto".to_string()),
+                },
+                FieldChange {
+                    field: "cityid".to_string(),
+                    before: Some("3".to_string()),
+                    after: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_should_record_set_fields_of_new_item() {
+        let mut changes = FieldChanges::new();
+
+        changes
+            .compare("name", None::<&String>, &"Rust".to_string())
+            .compare("photo_link", None::<&Option<String>>, &None::<String>);
+
+        assert_eq!(
+            changes.into_vec(),
+            vec![FieldChange {
+                field: "name".to_string(),
+                before: None,
+                after: Some("Rust".to_string()),
+            }]
+        );
+    }
+}
@@ -0,0 +1,12 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+
+use super::model::{FieldChangeCreateModel, FieldChangeModel};
+
+#[async_trait]
+pub trait FieldChangeRepository: Send + Sync {
+    /// Every change recorded for an item, oldest first.
+    async fn find_by_extid(&self, entity: &str, extid: &str) -> Result<Vec<FieldChangeModel>, DomainError>;
+    async fn insert_many(&self, changes: &[FieldChangeCreateModel]) -> Result<(), DomainError>;
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    error::DomainError,
+    field_change::{model::FieldChangeModel, repository::FieldChangeRepository},
+};
+
+pub async fn execute(
+    field_change_repository: Arc<dyn FieldChangeRepository>,
+    entity: &str,
+    extid: &str,
+) -> Result<Vec<FieldChangeModel>, DomainError> {
+    field_change_repository.find_by_extid(entity, extid).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::field_change::model::FieldChangeCreateModel;
+
+    mock! {
+        pub FakeFieldChangeRepository { }
+
+        #[async_trait]
+        impl FieldChangeRepository for FakeFieldChangeRepository {
+            async fn find_by_extid(&self, entity: &str, extid: &str) -> Result<Vec<FieldChangeModel>, DomainError>;
+            async fn insert_many(&self, changes: &[FieldChangeCreateModel]) -> Result<(), DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_changes_finded() {
+        let mut field_change_repository = MockFakeFieldChangeRepository::new();
+        field_change_repository
+            .expect_find_by_extid()
+            .withf(|entity, extid| entity == "event" && extid == "m297517437")
+            .return_once(|_, _| Ok(vec![FieldChangeModel::mock_default()]));
+
+        let changes = execute(Arc::new(field_change_repository), "event", "m297517437").await.unwrap();
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "venue");
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_repository_failed() {
+        let mut field_change_repository = MockFakeFieldChangeRepository::new();
+        field_change_repository
+            .expect_find_by_extid()
+            .return_once(|_, _| Err(DomainError::InternalServerError("Database down".to_string())));
+
+        assert!(execute(Arc::new(field_change_repository), "event", "m297517437").await.is_err());
+    }
+}
@@ -1,9 +1,9 @@
 use async_trait::async_trait;
-use crate::domain::error::DomainError;
+use crate::domain::{dead_letter::model::Fetched, error::DomainError};
 
 use super::model::{ GroupCreateModel};
 
 #[async_trait]
-pub trait GroupAdapter {
-    async fn fetch(&self, names: Vec<String>) -> Result<Vec<GroupCreateModel>, DomainError>;
+pub trait GroupAdapter: Send + Sync {
+    async fn fetch(&self, names: Vec<String>) -> Result<Fetched<GroupCreateModel>, DomainError>;
 }
\ No newline at end of file
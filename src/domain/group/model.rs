@@ -4,8 +4,12 @@ use crate::api::utils::random_number;
 use crate::api::utils::random_string;
 use crate::domain::article::model::Guidable;
 use crate::domain::article::model::Processable;
+use crate::domain::field_change::model::{Diffable, FieldChange, FieldChanges};
 use serde::Serialize;
-use sha2::{Sha256, Digest};
+use crate::domain::checksum::Checksum;
+
+// Bump when the mapping of fetched groups changes, to rewrite them all
+const CHECKSUM_VERSION: u32 = 1;
 
 use chrono::{DateTime, Utc};
 #[derive(Debug, Clone, Serialize)]
@@ -30,6 +34,8 @@ pub struct GroupCreateModel {
     pub photo_link: Option<String>,
     pub thumb_link: Option<String>,
     pub cityextid: String,
+    #[serde(skip)]
+    pub registered_name: String,
 }
 impl GroupCreateModel {
     pub fn new(
@@ -48,7 +54,6 @@ impl GroupCreateModel {
             extid,
             name,
             description,
-            slug,
             active,
             private,
             members,
@@ -57,10 +62,19 @@ impl GroupCreateModel {
             highres_link: image.highres_link,
             photo_link: image.photo_link,
             thumb_link: image.thumb_link,
+            registered_name: slug.clone(),
+            slug,
             cityextid,
         }
     }
 
+    /// Name the group is registered under, which scopes its sync instead of
+    /// its slug.
+    pub fn with_registered_name(mut self, registered_name: &str) -> Self {
+        self.registered_name = registered_name.to_string();
+        self
+    }
+
     pub fn set_cityid(&mut self,cityid: i32) -> &mut Self {
         self.cityid = Some(cityid);
         self
@@ -89,13 +103,19 @@ impl GroupCreateModel {
 
 impl Processable for GroupCreateModel {
     fn get_checksum(&self) -> String {
-        let model_string = serde_json::to_string(&self)
-        .expect("Failed to serialize GroupCreateModel to JSON");
-
-        let mut hasher = Sha256::new();
-        hasher.update(model_string);
-        let result = hasher.finalize();
-        format!("{:x}", result)
+        Checksum::new(CHECKSUM_VERSION)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("slug", &self.slug)
+            .field("active", &self.active)
+            .field("private", &self.private)
+            .field("members", &self.members)
+            .field("cityid", &self.cityid)
+            .field("organizer", &self.organizer)
+            .field("highres_link", &self.highres_link)
+            .field("photo_link", &self.photo_link)
+            .field("thumb_link", &self.thumb_link)
+            .finish()
     }
 }
 
@@ -126,6 +146,7 @@ impl GroupCreateModel {
             highres_link: Some("".to_string()),
             photo_link: Some("".to_string()),
             thumb_link: Some("".to_string()),
+            registered_name: random_string(10),
         }
     }
 }
@@ -177,6 +198,25 @@ impl GroupUpdateModel {
         }
     }
 }
+impl Diffable<GroupModel> for GroupUpdateModel {
+    fn changed_fields(&self, current: Option<&GroupModel>) -> Vec<FieldChange> {
+        let mut changes = FieldChanges::new();
+        changes
+            .compare("name", current.map(|current| &current.name), &self.name)
+            .compare("description", current.map(|current| &current.description), &self.description)
+            .compare("slug", current.map(|current| &current.slug), &self.slug)
+            .compare("active", current.map(|current| &current.active), &self.active)
+            .compare("private", current.map(|current| &current.private), &self.private)
+            .compare("members", current.map(|current| &current.members), &self.members)
+            .compare("cityid", current.map(|current| &current.cityid), &self.cityid)
+            .compare("organizer", current.map(|current| &current.organizer), &self.organizer)
+            .compare("highres_link", current.map(|current| &current.highres_link), &self.highres_link)
+            .compare("photo_link", current.map(|current| &current.photo_link), &self.photo_link)
+            .compare("thumb_link", current.map(|current| &current.thumb_link), &self.thumb_link);
+        changes.into_vec()
+    }
+}
+
 #[cfg(test)]
 impl GroupUpdateModel {
     pub fn mock_default() -> Self {
@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+
+#[async_trait]
+pub trait HealthRepository: Send + Sync {
+    /// Fails when the database cannot serve a query.
+    async fn ping(&self) -> Result<(), DomainError>;
+}
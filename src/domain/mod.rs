@@ -1,4 +1,5 @@
 pub mod error;
+pub mod checksum;
 
 pub mod diff_article;
 pub mod diff_group;
@@ -7,6 +8,11 @@ pub mod registered_author;
 pub mod registered_group;
 pub mod article;
 pub mod city;
+pub mod dead_letter;
 pub mod event;
+pub mod field_change;
+pub mod health;
 pub mod state;
-pub mod group;
\ No newline at end of file
+pub mod group;
+pub mod sync_lock;
+pub mod sync_run;
\ No newline at end of file
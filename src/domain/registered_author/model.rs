@@ -9,15 +9,18 @@ use crate::api::utils::random_string;
 pub struct RegisteredAuthorCreateModel {
     pub source: String,
     pub name: String,
+    pub feed_url: Option<String>,
 }
 impl RegisteredAuthorCreateModel {
     pub fn new(
         name: String, 
         source: String,
+        feed_url: Option<String>,
     ) -> Self {
         Self {
             name,
             source,
+            feed_url,
         }
     }
 }
@@ -28,6 +31,7 @@ impl RegisteredAuthorCreateModel {
         Self {
             source: "medium".to_string(),
             name: random_string(10),
+            feed_url: None,
         }
     }
 }
@@ -37,6 +41,8 @@ pub struct RegisteredAuthorModel {
     pub registered_authorid: i32,
     pub source: String,
     pub name: String,
+    /// RSS or Atom feed of the author, read instead of the feed of its source.
+    pub feed_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
 }
@@ -47,6 +53,7 @@ impl RegisteredAuthorModel {
             registered_authorid: random_number(),
             source: "medium".to_string(),
             name: random_string(10),
+            feed_url: None,
             created_at: DateTime::default(),
             updated_at: Some(DateTime::default()),
         }
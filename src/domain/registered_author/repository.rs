@@ -6,7 +6,7 @@ use crate::{domain::error::DomainError};
 use super::model::{RegisteredAuthorCreateModel, RegisteredAuthorModel};
 
 #[async_trait]
-pub trait RegisteredAuthorRepository {
+pub trait RegisteredAuthorRepository: Send + Sync {
     async fn find(
         &self,
         name: &Option<String>,
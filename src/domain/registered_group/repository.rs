@@ -6,7 +6,7 @@ use crate::{domain::error::DomainError};
 use super::model::{RegisteredGroupCreateModel, RegisteredGroupModel};
 
 #[async_trait]
-pub trait RegisteredGroupRepository {
+pub trait RegisteredGroupRepository: Send + Sync {
     async fn find(
         &self,
         name: &Option<String>,
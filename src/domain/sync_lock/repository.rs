@@ -0,0 +1,16 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+
+/// Exclusive right to run a job, held until released or dropped.
+#[async_trait]
+pub trait SyncLock: Send {
+    async fn release(&mut self) -> Result<(), DomainError>;
+}
+
+/// Per-job locks shared by every running instance of the service.
+#[async_trait]
+pub trait SyncLockRepository: Send + Sync {
+    /// Takes the lock of `job`, or `None` if another instance holds it.
+    async fn try_acquire(&self, job: &str) -> Result<Option<Box<dyn SyncLock>>, DomainError>;
+}
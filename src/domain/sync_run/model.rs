@@ -0,0 +1,143 @@
+use std::ops::AddAssign;
+
+use chrono::{DateTime, Utc};
+
+#[cfg(test)]
+use crate::api::utils::random_number;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncRunStatus {
+    Running,
+    Succeeded,
+    Failed,
+    Interrupted,
+}
+
+impl SyncRunStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SyncRunStatus::Running => "running",
+            SyncRunStatus::Succeeded => "succeeded",
+            SyncRunStatus::Failed => "failed",
+            SyncRunStatus::Interrupted => "interrupted",
+        }
+    }
+}
+
+impl From<&str> for SyncRunStatus {
+    fn from(status: &str) -> Self {
+        match status {
+            "running" => SyncRunStatus::Running,
+            "succeeded" => SyncRunStatus::Succeeded,
+            "interrupted" => SyncRunStatus::Interrupted,
+            _ => SyncRunStatus::Failed,
+        }
+    }
+}
+
+/// Items written by a sync pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SyncReport {
+    pub inserted: i32,
+    pub updated: i32,
+    pub unchanged: i32,
+    /// Items deleted after missing from their source for too many runs.
+    pub deleted: i32,
+}
+
+impl AddAssign for SyncReport {
+    fn add_assign(&mut self, other: Self) {
+        self.inserted += other.inserted;
+        self.updated += other.updated;
+        self.unchanged += other.unchanged;
+        self.deleted += other.deleted;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyncRunCreateModel {
+    pub job: String,
+}
+impl SyncRunCreateModel {
+    pub fn new(job: String) -> Self {
+        Self { job }
+    }
+}
+
+#[cfg(test)]
+impl SyncRunCreateModel {
+    pub fn mock_default() -> Self {
+        Self {
+            job: "articles".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncRunUpdateModel {
+    pub status: SyncRunStatus,
+    pub report: Option<SyncReport>,
+    pub error: Option<String>,
+}
+impl SyncRunUpdateModel {
+    pub fn succeeded(report: SyncReport) -> Self {
+        Self {
+            status: SyncRunStatus::Succeeded,
+            report: Some(report),
+            error: None,
+        }
+    }
+
+    pub fn failed(error: String) -> Self {
+        Self {
+            status: SyncRunStatus::Failed,
+            report: None,
+            error: Some(error),
+        }
+    }
+
+    /// Run stopped by a shutdown, with what it wrote before stopping if known.
+    pub fn interrupted(report: Option<SyncReport>, error: Option<String>) -> Self {
+        Self {
+            status: SyncRunStatus::Interrupted,
+            report,
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+impl SyncRunUpdateModel {
+    pub fn mock_default() -> Self {
+        Self::succeeded(SyncReport {
+            inserted: 1,
+            updated: 2,
+            unchanged: 3,
+            deleted: 0,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SyncRunModel {
+    pub sync_runid: i32,
+    pub status: SyncRunStatus,
+    pub report: Option<SyncReport>,
+    pub error: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl SyncRunModel {
+    pub fn mock_default() -> Self {
+        Self {
+            sync_runid: random_number(),
+            status: SyncRunStatus::Running,
+            report: None,
+            error: None,
+            started_at: DateTime::default(),
+            finished_at: None,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use async_trait::async_trait;
+
+use crate::domain::error::DomainError;
+
+use super::model::{SyncRunCreateModel, SyncRunModel, SyncRunUpdateModel};
+
+#[async_trait]
+pub trait SyncRunRepository: Send + Sync {
+    async fn find(
+        &self,
+        job: &Option<String>,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+    async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+    async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+    async fn insert(
+        &self,
+        sync_run_create_model: &SyncRunCreateModel,
+    ) -> Result<SyncRunModel, DomainError>;
+    async fn update_by_id(
+        &self,
+        id: &i32,
+        sync_run_update_model: &SyncRunUpdateModel,
+    ) -> Result<SyncRunModel, DomainError>;
+}
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    sync_run::{model::{SyncRunCreateModel, SyncRunModel}, repository::SyncRunRepository},
+    error::DomainError,
+};
+
+pub async fn execute(
+    sync_run_repository: Arc<dyn SyncRunRepository>,
+    sync_run_create_model: SyncRunCreateModel,
+) -> Result<SyncRunModel, DomainError> {
+    let sync_run = sync_run_repository.insert(&sync_run_create_model).await?;
+    Ok(sync_run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::sync_run::model::SyncRunUpdateModel;
+
+    mock! {
+        pub FakeSyncRunRepository { }
+
+        #[async_trait]
+        impl SyncRunRepository for FakeSyncRunRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+            async fn insert(&self,sync_run_create_model: &SyncRunCreateModel) -> Result<SyncRunModel, DomainError>;
+            async fn update_by_id(&self,id: &i32,sync_run_update_model: &SyncRunUpdateModel) -> Result<SyncRunModel, DomainError>;
+            async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_sync_run_created() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_insert()
+            .return_once(|_| Ok(SyncRunModel::mock_default()));
+
+        let result = execute(
+            Arc::new(sync_run_repository),
+            SyncRunCreateModel::mock_default(),
+        )
+        .await;
+
+        match result {
+            Ok(_) => {}
+            Err(err) => unreachable!("{err}"),
+        }
+    }
+}
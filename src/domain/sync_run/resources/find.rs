@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    sync_run::{model::SyncRunModel, repository::SyncRunRepository},
+    error::DomainError,
+};
+
+pub async fn execute(
+    sync_run_repository: Arc<dyn SyncRunRepository>,
+    job: Option<String>,
+    page: u32,
+    page_size: u32,
+) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError> {
+    let sync_runs = sync_run_repository.find(&job, &page, &page_size).await?;
+
+    if sync_runs.is_some() {
+        return Ok(sync_runs);
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::sync_run::model::{SyncRunCreateModel, SyncRunUpdateModel};
+
+    mock! {
+        pub FakeSyncRunRepository { }
+
+        #[async_trait]
+        impl SyncRunRepository for FakeSyncRunRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+            async fn insert(&self,sync_run_create_model: &SyncRunCreateModel) -> Result<SyncRunModel, DomainError>;
+            async fn update_by_id(&self,id: &i32,sync_run_update_model: &SyncRunUpdateModel) -> Result<SyncRunModel, DomainError>;
+            async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_sync_runs_finded() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_find()
+            .return_once(|_, _, _| Ok(Some((vec![SyncRunModel::mock_default()], 1))));
+
+        let (sync_runs, count) = execute(Arc::new(sync_run_repository), Some("articles".to_string()), 1, 12)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!sync_runs.is_empty());
+        assert!(count == 1);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_finded() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+        sync_run_repository
+            .expect_find()
+            .return_once(|_, _, _| Ok(None));
+
+        let response = execute(Arc::new(sync_run_repository), None, 1, 12)
+            .await
+            .unwrap();
+
+        assert!(response.is_none());
+    }
+}
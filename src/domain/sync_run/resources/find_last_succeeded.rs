@@ -0,0 +1,74 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    sync_run::{model::SyncRunModel, repository::SyncRunRepository},
+    error::DomainError,
+};
+
+pub async fn execute(
+    sync_run_repository: Arc<dyn SyncRunRepository>,
+    job: String,
+) -> Result<Option<SyncRunModel>, DomainError> {
+    let sync_run = sync_run_repository.find_last_succeeded(&job).await?;
+    Ok(sync_run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    use crate::domain::sync_run::model::{SyncRunCreateModel, SyncRunStatus, SyncRunUpdateModel};
+
+    mock! {
+        pub FakeSyncRunRepository { }
+
+        #[async_trait]
+        impl SyncRunRepository for FakeSyncRunRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+            async fn insert(&self,sync_run_create_model: &SyncRunCreateModel) -> Result<SyncRunModel, DomainError>;
+            async fn update_by_id(&self,id: &i32,sync_run_update_model: &SyncRunUpdateModel) -> Result<SyncRunModel, DomainError>;
+            async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_last_succeeded_sync_run() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_find_last_succeeded()
+            .withf(|job| job == "articles")
+            .return_once(|_| {
+                Ok(Some(SyncRunModel {
+                    status: SyncRunStatus::Succeeded,
+                    ..SyncRunModel::mock_default()
+                }))
+            });
+
+        let sync_run = execute(Arc::new(sync_run_repository), "articles".to_string())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(sync_run.status, SyncRunStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_none_never_succeeded() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_find_last_succeeded()
+            .return_once(|_| Ok(None));
+
+        let response = execute(Arc::new(sync_run_repository), "articles".to_string())
+            .await
+            .unwrap();
+
+        assert!(response.is_none());
+    }
+}
@@ -0,0 +1,4 @@
+pub mod create;
+pub mod find;
+pub mod find_last_succeeded;
+pub mod update_by_id;
@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use crate::domain::{
+    sync_run::{
+        model::{SyncRunModel, SyncRunUpdateModel},
+        repository::SyncRunRepository,
+    },
+    error::DomainError,
+};
+
+pub async fn execute(
+    sync_run_repository: Arc<dyn SyncRunRepository>,
+    id: i32,
+    sync_run_update_model: SyncRunUpdateModel,
+) -> Result<SyncRunModel, DomainError> {
+    let has_sync_run = sync_run_repository.find_by_id(&id).await?;
+    if has_sync_run.is_none() {
+        return Err(DomainError::NotFound(String::from("SyncRun id not found")));
+    }
+
+    let sync_run = sync_run_repository
+        .update_by_id(&id, &sync_run_update_model)
+        .await?;
+
+    Ok(sync_run)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{domain::sync_run::model::SyncRunCreateModel, api::utils::random_number};
+
+    use super::*;
+
+    use async_trait::async_trait;
+    use mockall::mock;
+
+    mock! {
+        pub FakeSyncRunRepository { }
+
+        #[async_trait]
+        impl SyncRunRepository for FakeSyncRunRepository {
+            async fn find(&self,job: &Option<String>,page: &u32,page_size: &u32) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError>;
+            async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError>;
+            async fn insert(&self,sync_run_create_model: &SyncRunCreateModel) -> Result<SyncRunModel, DomainError>;
+            async fn update_by_id(&self,id: &i32,sync_run_update_model: &SyncRunUpdateModel) -> Result<SyncRunModel, DomainError>;
+            async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError>;
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_sync_run_updated() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+
+        sync_run_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(Some(SyncRunModel::mock_default())));
+
+        sync_run_repository
+            .expect_update_by_id()
+            .return_once(|_, _| Ok(SyncRunModel::mock_default()));
+
+        let result = execute(
+            Arc::new(sync_run_repository),
+            random_number(),
+            SyncRunUpdateModel::mock_default(),
+        )
+        .await;
+
+        match result {
+            Ok(_) => {}
+            Err(err) => unreachable!("{err}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn it_should_return_error_not_found_sync_run() {
+        let mut sync_run_repository = MockFakeSyncRunRepository::new();
+        sync_run_repository
+            .expect_find_by_id()
+            .return_once(|_| Ok(None));
+
+        let result = execute(
+            Arc::new(sync_run_repository),
+            random_number(),
+            SyncRunUpdateModel::mock_default(),
+        )
+        .await;
+
+        match result {
+            Err(DomainError::NotFound(_)) => {}
+            _ => unreachable!(),
+        }
+    }
+}
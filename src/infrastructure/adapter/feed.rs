@@ -0,0 +1,255 @@
+use chrono::{DateTime, Utc};
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use serde::Serialize;
+
+/// An RSS item or Atom entry, with the fields articles are mapped from.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct FeedEntry {
+    /// RSS `guid` or Atom `id`.
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub link: Option<String>,
+    /// RSS `pubDate` or Atom `published`.
+    pub published: Option<String>,
+    /// Atom `updated`.
+    pub updated: Option<String>,
+    /// RSS `description` or Atom `summary`.
+    pub summary: Option<String>,
+    /// RSS `content:encoded` or Atom `content`.
+    pub content: Option<String>,
+    pub categories: Vec<String>,
+    /// First image enclosure, `media:content` or `media:thumbnail`.
+    pub image: Option<String>,
+}
+
+impl FeedEntry {
+    /// Publication date, falling back on the Atom `updated` date.
+    pub fn publish_at(&self) -> Result<DateTime<Utc>, String> {
+        let date = self
+            .published
+            .as_ref()
+            .or(self.updated.as_ref())
+            .ok_or("Missing publication date")?;
+        parse_date(date)
+    }
+}
+
+/// Parses RSS (`pubDate`) and Atom (RFC 3339) dates.
+pub fn parse_date(date: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc2822(date)
+        .or_else(|_| DateTime::parse_from_rfc3339(date))
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|err| format!("Invalid date {}: {}", date, err))
+}
+
+/// Reads the entries of an RSS 2.0 (or 1.0) or Atom feed.
+pub fn parse(xml: &str) -> Result<Vec<FeedEntry>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut path: Vec<String> = Vec::new();
+    let mut entries: Vec<FeedEntry> = Vec::new();
+    let mut entry: Option<FeedEntry> = None;
+    let mut text = String::new();
+    let mut root_checked = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|err| format!("Invalid XML at {}: {}", reader.buffer_position(), err))?;
+        match event {
+            Event::Start(element) => {
+                let name = element_name(&element);
+                if !root_checked {
+                    check_root(&name)?;
+                    root_checked = true;
+                }
+                if is_entry(&name) {
+                    entry = Some(FeedEntry::default());
+                } else if let Some(entry) = entry.as_mut() {
+                    read_attributes(entry, &name, &element);
+                }
+                path.push(name);
+                text.clear();
+            }
+            Event::Empty(element) => {
+                let name = element_name(&element);
+                if !root_checked {
+                    check_root(&name)?;
+                    root_checked = true;
+                }
+                if let Some(entry) = entry.as_mut() {
+                    read_attributes(entry, &name, &element);
+                }
+            }
+            Event::Text(content) => {
+                let content = content
+                    .unescape()
+                    .map_err(|err| format!("Invalid text at {}: {}", reader.buffer_position(), err))?;
+                text.push_str(&content);
+            }
+            Event::CData(content) => text.push_str(&String::from_utf8_lossy(&content.into_inner())),
+            Event::End(_) => {
+                let name = path.pop().unwrap_or_default();
+                if is_entry(&name) {
+                    entries.extend(entry.take());
+                } else if let Some(entry) = entry.as_mut() {
+                    // Only the direct children of an entry, not its author name for example
+                    if path.last().map(String::as_str).is_some_and(is_entry) {
+                        read_text(entry, &name, std::mem::take(&mut text));
+                    }
+                }
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !root_checked {
+        return Err("Empty feed".to_string());
+    }
+    Ok(entries)
+}
+
+fn element_name(element: &BytesStart) -> String {
+    String::from_utf8_lossy(element.name().as_ref()).to_string()
+}
+
+fn check_root(name: &str) -> Result<(), String> {
+    match name {
+        "rss" | "feed" | "rdf:RDF" => Ok(()),
+        name => Err(format!("Not an RSS or Atom feed, root element is {}", name)),
+    }
+}
+
+fn is_entry(name: &str) -> bool {
+    name == "item" || name == "entry"
+}
+
+fn attribute(element: &BytesStart, key: &str) -> Option<String> {
+    element
+        .try_get_attribute(key)
+        .ok()
+        .flatten()
+        .and_then(|attribute| attribute.unescape_value().ok())
+        .map(|value| value.to_string())
+}
+
+fn read_attributes(entry: &mut FeedEntry, name: &str, element: &BytesStart) {
+    match name {
+        // Atom links carry their url as an attribute, RSS links as text
+        "link" => {
+            let rel = attribute(element, "rel");
+            if let (Some(href), None | Some("alternate")) = (attribute(element, "href"), rel.as_deref()) {
+                entry.link.get_or_insert(href);
+            }
+        }
+        "category" => entry.categories.extend(attribute(element, "term")),
+        "enclosure" | "media:content" => {
+            let kind = attribute(element, "type").or_else(|| attribute(element, "medium"));
+            if kind.is_some_and(|kind| kind.starts_with("image")) && entry.image.is_none() {
+                entry.image = attribute(element, "url");
+            }
+        }
+        "media:thumbnail" if entry.image.is_none() => entry.image = attribute(element, "url"),
+        _ => {}
+    }
+}
+
+fn read_text(entry: &mut FeedEntry, name: &str, text: String) {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return;
+    }
+
+    match name {
+        "guid" | "id" => entry.id = Some(text),
+        "title" => entry.title = Some(text),
+        "link" => {
+            entry.link.get_or_insert(text);
+        }
+        "pubDate" | "published" | "dc:date" => entry.published = Some(text),
+        "updated" | "atom:updated" => entry.updated = Some(text),
+        "description" | "summary" => entry.summary = Some(text),
+        "content:encoded" | "content" => entry.content = Some(text),
+        "category" => entry.categories.push(text),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RSS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+        <rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+            <channel>
+                <title>Lab notes</title>
+                <item>
+                    <title>Graphs &amp; tables</title>
+                    <link>https://lab.example.ca/graphs</link>
+                    <guid isPermaLink="false">https://lab.example.ca/?p=12</guid>
+                    <pubDate>Tue, 10 Oct 2023 14:00:00 GMT</pubDate>
+                    <category><![CDATA[data]]></category>
+                    <description>Short</description>
+                    <content:encoded><![CDATA[<p><img src="https://lab.example.ca/cover.png">Long</p>]]></content:encoded>
+                    <enclosure url="https://lab.example.ca/cover.jpg" type="image/jpeg" length="0"/>
+                </item>
+            </channel>
+        </rss>"#;
+
+    const ATOM: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+        <feed xmlns="http://www.w3.org/2005/Atom">
+            <title>Engineering</title>
+            <entry>
+                <title type="html">Scaling Postgres</title>
+                <link rel="replies" href="https://eng.example.ca/scaling#comments"/>
+                <link href="https://eng.example.ca/scaling"/>
+                <id>urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a</id>
+                <updated>2023-10-11T09:30:00-04:00</updated>
+                <author><name>Jo</name></author>
+                <category term="databases"/>
+                <summary>Partitioning</summary>
+            </entry>
+        </feed>"#;
+
+    #[test]
+    fn it_should_parse_rss_items() {
+        let entries = parse(RSS).unwrap();
+
+        assert_eq!(entries, vec![FeedEntry {
+            id: Some("https://lab.example.ca/?p=12".to_string()),
+            title: Some("Graphs & tables".to_string()),
+            link: Some("https://lab.example.ca/graphs".to_string()),
+            published: Some("Tue, 10 Oct 2023 14:00:00 GMT".to_string()),
+            updated: None,
+            summary: Some("Short".to_string()),
+            content: Some(r#"<p><img src="https://lab.example.ca/cover.png">Long</p>"#.to_string()),
+            categories: vec!["data".to_string()],
+            image: Some("https://lab.example.ca/cover.jpg".to_string()),
+        }]);
+        assert_eq!(entries[0].publish_at().unwrap().to_rfc3339(), "2023-10-10T14:00:00+00:00");
+    }
+
+    #[test]
+    fn it_should_parse_atom_entries() {
+        let entries = parse(ATOM).unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].id.as_deref(), Some("urn:uuid:1225c695-cfb8-4ebb-aaaa-80da344efa6a"));
+        assert_eq!(entries[0].link.as_deref(), Some("https://eng.example.ca/scaling"));
+        assert_eq!(entries[0].categories, vec!["databases".to_string()]);
+        assert_eq!(entries[0].summary.as_deref(), Some("Partitioning"));
+        assert_eq!(entries[0].publish_at().unwrap().to_rfc3339(), "2023-10-11T13:30:00+00:00");
+    }
+
+    #[test]
+    fn it_should_return_error_not_a_feed() {
+        assert!(parse("<html><body>Not found</body></html>").is_err());
+        assert!(parse("<rss><channel><item></rss>").is_err());
+    }
+}
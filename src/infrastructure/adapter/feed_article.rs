@@ -0,0 +1,239 @@
+use async_trait::async_trait;
+
+use crate::{domain::{article::{adapter::ArticleAdapter, model::ArticleCreateModel}, dead_letter::model::{FetchFailure, Fetched}, error::DomainError, registered_author::model::RegisteredAuthorModel}, infrastructure::adapter::http};
+use regex::Regex;
+use url::Url;
+
+use super::feed::{self, FeedEntry};
+
+/// Feed of the Medium authors, `{name}` being the author name.
+pub const MEDIUM_FEED_URL: &str = "https://medium.com/feed/@{name}";
+
+// Feeds don't tell how long an article takes to read
+pub const READING_TIME_MINUTES: i32 = 5;
+
+pub fn remove_html_tags(input: &str) -> String {
+    let re = Regex::new(r"<[^>]+>").unwrap();
+    re.replace_all(input, "").to_string()
+}
+
+fn modify_medium_image_url(url_str: &str,fit: u16) -> Result<String, url::ParseError> {
+    let url = Url::parse(url_str)?;
+
+    // Check if the host is a Medium image URL
+    if url.host_str() != Some("cdn-images-1.medium.com") {
+        return Ok(url.to_string()); // Not a Medium image URL
+    }
+
+    let re = Regex::new(r"max\/[0-9]+").unwrap();
+    Ok(re.replace_all(url_str, "max/".to_owned() + &fit.to_string()).to_string())
+}
+
+pub fn extract_image_link(html: &str) -> Option<String> {
+    // Regular expression pattern to match the src attribute of the img tag
+    let pattern = r#"src="([^"]+)""#;
+    let re = Regex::new(pattern).unwrap();
+
+    // Find the first match in the HTML string
+    if let Some(capture) = re.captures(html) {
+        // Extract the captured group containing the link
+        if let Some(link) = capture.get(1) {
+            return Some(link.as_str().to_owned());
+        }
+    }
+    None
+}
+
+// Maps one feed entry, failing on a missing id, title, link or date
+fn to_article(entry: &FeedEntry, author: &RegisteredAuthorModel) -> Result<ArticleCreateModel, String> {
+    let link = entry.link.clone().ok_or("Missing link")?;
+    let extid = entry.id.clone().unwrap_or_else(|| link.clone());
+    let title = entry.title.clone().ok_or("Missing title")?;
+    let publish_at = entry.publish_at()?;
+
+    let html = entry.content.as_ref().or(entry.summary.as_ref());
+    let image = entry.image.clone().or_else(|| html.and_then(|html| extract_image_link(html)));
+    let (highres_link, photo_link, thumb_link) = match image {
+        Some(image) => {
+            let image_link = |fit| modify_medium_image_url(&image, fit)
+                .map_err(|err| format!("Invalid image link {}: {}", image, err));
+            (Some(image_link(800)?), Some(image_link(600)?), Some(image_link(400)?))
+        }
+        None => (None, None, None),
+    };
+
+    Ok(ArticleCreateModel::new(
+        title,
+        html.map(|html| remove_html_tags(html)),
+        extid,
+        READING_TIME_MINUTES,
+        author.source.clone(),
+        link,
+        author.name.clone(),
+        publish_at,
+        highres_link,
+        photo_link,
+        thumb_link,
+    ))
+}
+
+/// Reads the articles of an author from an RSS or Atom feed: the feed URL
+/// registered with the author, or the default feed of its source.
+pub struct FeedArticleAdapter {
+    client: reqwest::Client,
+    default_feed_url: Option<&'static str>,
+}
+
+impl FeedArticleAdapter {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            default_feed_url: None,
+        }
+    }
+
+    /// Feed read for the authors registered without one, `{name}` being
+    /// replaced by the author name.
+    pub fn with_default_feed_url(mut self, default_feed_url: &'static str) -> Self {
+        self.default_feed_url = Some(default_feed_url);
+        self
+    }
+
+    fn feed_url(&self, author: &RegisteredAuthorModel) -> Option<String> {
+        author
+            .feed_url
+            .clone()
+            .or_else(|| self.default_feed_url.map(|feed_url| feed_url.replace("{name}", &author.name)))
+    }
+}
+
+/// Fetches the feed of `author` at `url` and maps its entries with
+/// `to_article`, the entries that fail to map or a feed that fails to fetch
+/// or parse being returned as failures.
+pub async fn fetch_feed(
+    client: &reqwest::Client,
+    url: &str,
+    author: &RegisteredAuthorModel,
+    to_article: fn(&FeedEntry, &RegisteredAuthorModel) -> Result<ArticleCreateModel, String>,
+) -> Result<Fetched<ArticleCreateModel>, DomainError> {
+    let mut fetched = Fetched::new();
+    let failure = |err: &dyn std::fmt::Display| FetchFailure::scope(&author.source, &author.name, err);
+
+    let response = match http::send(client.get(url)).await {
+        Ok(response) => response,
+        Err(err) => {
+            fetched.failures.push(failure(&format!("Failed to fetch feed {}: {}", url, err)));
+            return Ok(fetched);
+        }
+    };
+    let success = response.status().is_success();
+    let xml = match response.text().await {
+        Ok(xml) => xml,
+        Err(err) => {
+            fetched.failures.push(failure(&format!("Failed to read feed {}: {}", url, err)));
+            return Ok(fetched);
+        }
+    };
+    if !success {
+        fetched.failures.push(failure(&format!("Failed to fetch feed {}", url)).with_payload(&xml));
+        return Ok(fetched);
+    }
+
+    let entries = match feed::parse(&xml) {
+        Ok(entries) => entries,
+        Err(err) => {
+            fetched.failures.push(failure(&format!("Failed to parse feed {}: {}", url, err)).with_payload(&xml));
+            return Ok(fetched);
+        }
+    };
+
+    for entry in entries {
+        match to_article(&entry, author) {
+            Ok(article) => fetched.items.push(article),
+            Err(err) => {
+                let key = entry.id.as_ref().or(entry.link.as_ref()).unwrap_or(&author.name);
+                let payload = serde_json::to_string(&entry).unwrap_or_default();
+                fetched.failures.push(FetchFailure::new(&author.source, &author.name, key, err).with_payload(&payload));
+            }
+        }
+    }
+
+    Ok(fetched)
+}
+
+#[async_trait]
+impl ArticleAdapter for FeedArticleAdapter {
+    async fn fetch(&self, author: &RegisteredAuthorModel) -> Result<Fetched<ArticleCreateModel>, DomainError> {
+        match self.feed_url(author) {
+            Some(url) => fetch_feed(&self.client, &url, author, to_article).await,
+            None => Ok(Fetched {
+                items: Vec::new(),
+                failures: vec![FetchFailure::scope(&author.source, &author.name, "No feed URL registered")],
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry() -> FeedEntry {
+        FeedEntry {
+            id: Some("https://medium.com/p/8f2a".to_string()),
+            title: Some("Decision intelligence".to_string()),
+            link: Some("https://kozyrkov.medium.com/decision-intelligence-8f2a".to_string()),
+            published: Some("Tue, 10 Oct 2023 14:00:00 GMT".to_string()),
+            content: Some(r#"<figure><img src="https://cdn-images-1.medium.com/max/1024/1*cover.png"></figure><p>Text</p>"#.to_string()),
+            ..FeedEntry::default()
+        }
+    }
+
+    #[test]
+    fn it_should_map_entry_with_resized_image() {
+        let author = RegisteredAuthorModel::mock_default();
+
+        let article = to_article(&entry(), &author).unwrap();
+
+        assert_eq!(article.extid, "https://medium.com/p/8f2a");
+        assert_eq!(article.source, "medium");
+        assert_eq!(article.author, author.name);
+        assert_eq!(article.description.as_deref(), Some("Text"));
+        assert_eq!(article.thumb_link.as_deref(), Some("https://cdn-images-1.medium.com/max/400/1*cover.png"));
+    }
+
+    #[test]
+    fn it_should_return_error_entry_without_date() {
+        let entry = FeedEntry {
+            published: None,
+            ..entry()
+        };
+
+        assert!(to_article(&entry, &RegisteredAuthorModel::mock_default()).is_err());
+    }
+
+    #[test]
+    fn it_should_prefer_registered_feed_url() {
+        let adapter = FeedArticleAdapter::new().with_default_feed_url(MEDIUM_FEED_URL);
+        let mut author = RegisteredAuthorModel::mock_default();
+        author.name = "kozyrkov".to_string();
+
+        assert_eq!(adapter.feed_url(&author).as_deref(), Some("https://medium.com/feed/@kozyrkov"));
+
+        author.feed_url = Some("https://lab.example.ca/feed.xml".to_string());
+        assert_eq!(adapter.feed_url(&author).as_deref(), Some("https://lab.example.ca/feed.xml"));
+        assert_eq!(FeedArticleAdapter::new().feed_url(&RegisteredAuthorModel::mock_default()), None);
+    }
+
+    #[tokio::test]
+    async fn it_should_return_scope_failure_when_feed_is_unreachable() {
+        let author = RegisteredAuthorModel::mock_default();
+
+        let fetched = fetch_feed(&reqwest::Client::new(), "http://127.0.0.1:9/feed", &author, to_article)
+            .await
+            .unwrap();
+
+        assert!(fetched.items.is_empty());
+        assert!(!fetched.is_complete(&author.name));
+    }
+}
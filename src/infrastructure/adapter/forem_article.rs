@@ -0,0 +1,216 @@
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::{domain::{article::{adapter::ArticleAdapter, model::ArticleCreateModel}, dead_letter::model::{FetchFailure, Fetched}, error::DomainError, registered_author::model::RegisteredAuthorModel}, infrastructure::adapter::http};
+
+use super::feed::parse_date;
+
+/// API of dev.to, the main Forem instance.
+pub const DEVTO_API_URL: &str = "https://dev.to/api";
+
+// Largest page the articles endpoint returns
+const PAGE_SIZE: usize = 1000;
+
+#[derive(Deserialize)]
+struct ForemArticle {
+    id: i64,
+    title: String,
+    description: Option<String>,
+    url: String,
+    cover_image: Option<String>,
+    published_at: Option<String>,
+    edited_at: Option<String>,
+    reading_time_minutes: i32,
+    #[serde(default)]
+    tag_list: Vec<String>,
+}
+
+// Ids are only unique within a Forem instance, so prefixed by its source
+fn extid(source: &str, id: impl std::fmt::Display) -> String {
+    format!("{}{}", source, id)
+}
+
+// Forem resizes the cover images it serves under `/dynamic/image/`, keeping
+// the 1000x420 ratio of the covers
+fn modify_forem_image_url(url: &str, width: u16) -> String {
+    let re = Regex::new(r"width=[0-9]+,height=[0-9]+").unwrap();
+    let height = u32::from(width) * 42 / 100;
+    re.replace(url, format!("width={},height={}", width, height).as_str()).to_string()
+}
+
+// Maps one article, failing on an unpublished one or a missing field
+fn to_article(value: &serde_json::Value, author: &RegisteredAuthorModel) -> Result<ArticleCreateModel, String> {
+    let forem_article = ForemArticle::deserialize(value).map_err(|err| format!("Invalid article: {}", err))?;
+
+    let publish_at = parse_date(forem_article.published_at.as_deref().ok_or("Not published")?)?;
+    let edited_at = forem_article.edited_at.as_deref().map(parse_date).transpose()?;
+    let image_link = |width| forem_article.cover_image.as_ref().map(|image| modify_forem_image_url(image, width));
+
+    Ok(ArticleCreateModel::new(
+        forem_article.title.clone(),
+        forem_article.description.clone().filter(|description| !description.is_empty()),
+        extid(&author.source, forem_article.id),
+        forem_article.reading_time_minutes,
+        author.source.clone(),
+        forem_article.url.clone(),
+        author.name.clone(),
+        publish_at,
+        image_link(800),
+        image_link(600),
+        image_link(400),
+    )
+    .with_tags(forem_article.tag_list)
+    .with_edited_at(edited_at))
+}
+
+/// Reads the published articles of a Forem user, the author name being its
+/// username.
+pub struct ForemArticleAdapter {
+    client: reqwest::Client,
+    api_url: &'static str,
+}
+
+impl ForemArticleAdapter {
+    pub fn new(api_url: &'static str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url,
+        }
+    }
+
+    fn page_url(&self, author: &RegisteredAuthorModel, page: usize) -> String {
+        format!("{}/articles?username={}&page={}&per_page={}", self.api_url, author.name, page, PAGE_SIZE)
+    }
+
+    async fn fetch_page(&self, author: &RegisteredAuthorModel, page: usize) -> Result<Vec<serde_json::Value>, FetchFailure> {
+        let failure = |err: &dyn std::fmt::Display| FetchFailure::scope(&author.source, &author.name, err);
+
+        let response = http::send(self.client.get(self.page_url(author, page))).await.map_err(|err| failure(&err))?;
+        let success = response.status().is_success();
+        let body = response.text().await.map_err(|err| failure(&err))?;
+
+        if !success {
+            return Err(failure(&format!("Failed to fetch page {} of articles", page)).with_payload(&body));
+        }
+
+        // Decoded one by one so a malformed article doesn't fail its whole author
+        serde_json::from_str(&body).map_err(|err| failure(&format!("Invalid articles: {}", err)).with_payload(&body))
+    }
+}
+
+#[async_trait]
+impl ArticleAdapter for ForemArticleAdapter {
+    async fn fetch(&self, author: &RegisteredAuthorModel) -> Result<Fetched<ArticleCreateModel>, DomainError> {
+        let mut fetched = Fetched::new();
+
+        for page in 1.. {
+            let values = match self.fetch_page(author, page).await {
+                Ok(values) => values,
+                Err(failure) => {
+                    // The articles of the pages read still sync, the failure
+                    // keeps the author from being swept
+                    fetched.failures.push(failure);
+                    break;
+                }
+            };
+
+            for value in &values {
+                match to_article(value, author) {
+                    Ok(article) => fetched.items.push(article),
+                    Err(err) => {
+                        let key = value
+                            .get("id")
+                            .and_then(|id| id.as_i64())
+                            .map_or_else(|| author.name.clone(), |id| extid(&author.source, id));
+                        fetched
+                            .failures
+                            .push(FetchFailure::new(&author.source, &author.name, &key, err).with_payload(&value.to_string()));
+                    }
+                }
+            }
+
+            if values.len() < PAGE_SIZE {
+                break;
+            }
+        }
+
+        Ok(fetched)
+    }
+
+    fn lists_all(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::article::model::Processable;
+
+    use super::*;
+
+    const ARTICLES: &str = include_str!("fixtures/devto_articles.json");
+
+    fn author() -> RegisteredAuthorModel {
+        let mut author = RegisteredAuthorModel::mock_default();
+        author.source = "devto".to_string();
+        author.name = "datanorth".to_string();
+        author
+    }
+
+    fn articles() -> Vec<Result<ArticleCreateModel, String>> {
+        let values: Vec<serde_json::Value> = serde_json::from_str(ARTICLES).unwrap();
+        values.iter().map(|value| to_article(value, &author())).collect()
+    }
+
+    #[test]
+    fn it_should_map_article_fields() {
+        let article = articles().remove(0).unwrap();
+
+        assert_eq!(article.name, "Partitioning Postgres tables without downtime");
+        assert_eq!(article.extid, "devto1624580");
+        assert_eq!(article.link, "https://dev.to/datanorth/partitioning-postgres-tables-without-downtime-4k1n");
+        assert_eq!(article.source, "devto");
+        assert_eq!(article.author, "datanorth");
+        assert_eq!(article.time_m, 9);
+        assert_eq!(article.tags, vec!["postgres", "database", "devops"]);
+        assert_eq!(article.publish_at.to_rfc3339(), "2023-10-10T13:00:45+00:00");
+        assert_eq!(article.edited_at.unwrap().to_rfc3339(), "2023-10-12T08:30:00+00:00");
+        assert_eq!(
+            article.thumb_link.as_deref(),
+            Some("https://media2.dev.to/dynamic/image/width=400,height=168,fit=cover,gravity=auto,format=auto/https%3A%2F%2Fdev-to-uploads.s3.amazonaws.com%2Fuploads%2Farticles%2Fpartitions.png")
+        );
+    }
+
+    #[test]
+    fn it_should_map_article_without_cover_or_edit() {
+        let article = articles().remove(1).unwrap();
+
+        assert_eq!(article.time_m, 4);
+        assert!(article.highres_link.is_none());
+        assert!(article.edited_at.is_none());
+    }
+
+    #[test]
+    fn it_should_change_checksum_edited_article() {
+        let article = articles().remove(0).unwrap();
+        let edited = article.clone().with_edited_at(parse_date("2023-10-14T09:00:00Z").ok());
+
+        assert_ne!(article.get_checksum(), edited.get_checksum());
+    }
+
+    #[test]
+    fn it_should_return_error_unpublished_article() {
+        assert_eq!(articles().remove(2).err().as_deref(), Some("Not published"));
+    }
+
+    #[test]
+    fn it_should_page_articles_by_username() {
+        let adapter = ForemArticleAdapter::new(DEVTO_API_URL);
+
+        assert_eq!(
+            adapter.page_url(&author(), 2),
+            "https://dev.to/api/articles?username=datanorth&page=2&per_page=1000"
+        );
+    }
+}
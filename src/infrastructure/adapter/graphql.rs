@@ -0,0 +1,141 @@
+use std::fmt;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::infrastructure::adapter::http;
+
+/// A failed GraphQL query, with the response body when there was one.
+#[derive(Debug)]
+pub struct GraphQlError {
+    pub message: String,
+    pub body: Option<String>,
+}
+impl GraphQlError {
+    fn new(message: impl fmt::Display, body: Option<&str>) -> Self {
+        Self {
+            message: message.to_string(),
+            body: body.map(str::to_string),
+        }
+    }
+}
+impl fmt::Display for GraphQlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+#[derive(Serialize)]
+struct Request<'a> {
+    query: &'a str,
+    variables: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct Response<D> {
+    data: Option<D>,
+    errors: Option<Vec<ResponseError>>,
+}
+
+#[derive(Deserialize)]
+struct ResponseError {
+    message: String,
+}
+
+/// Relay connection, the paginated lists of most GraphQL APIs.
+#[derive(Debug, Deserialize)]
+pub struct Connection<N> {
+    pub edges: Vec<Edge<N>>,
+    #[serde(rename = "pageInfo")]
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Edge<N> {
+    pub node: N,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    pub has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    pub end_cursor: Option<String>,
+}
+impl PageInfo {
+    /// Cursor of the next page, `None` on the last one.
+    pub fn next_cursor(&self) -> Option<&str> {
+        self.end_cursor.as_deref().filter(|_| self.has_next_page)
+    }
+}
+
+/// Posts queries to a GraphQL endpoint.
+pub struct GraphQlClient {
+    client: reqwest::Client,
+    url: &'static str,
+}
+
+impl GraphQlClient {
+    pub fn new(url: &'static str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url,
+        }
+    }
+
+    /// Runs `query` with its `variables`, decoding its `data` as `D`. A
+    /// response carrying errors fails, even with partial data.
+    pub async fn query<D: DeserializeOwned>(&self, query: &str, variables: serde_json::Value) -> Result<D, GraphQlError> {
+        let request = self.client.post(self.url).json(&Request { query, variables });
+        let response = http::send(request).await.map_err(|err| GraphQlError::new(err, None))?;
+        let status = response.status();
+        let body = response.text().await.map_err(|err| GraphQlError::new(err, None))?;
+
+        // GraphQL servers may answer errors with a 4xx status and a body
+        decode(&body).map_err(|err| match status.is_success() {
+            true => err,
+            false => GraphQlError::new(format!("{} ({})", err.message, status), Some(&body)),
+        })
+    }
+}
+
+fn decode<D: DeserializeOwned>(body: &str) -> Result<D, GraphQlError> {
+    let response: Response<D> = serde_json::from_str(body)
+        .map_err(|err| GraphQlError::new(format!("Invalid response: {}", err), Some(body)))?;
+
+    match (response.data, response.errors.unwrap_or_default()) {
+        (Some(data), errors) if errors.is_empty() => Ok(data),
+        (_, errors) if !errors.is_empty() => {
+            let messages: Vec<String> = errors.into_iter().map(|error| error.message).collect();
+            Err(GraphQlError::new(messages.join("; "), Some(body)))
+        }
+        _ => Err(GraphQlError::new("Missing data", Some(body))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Data {
+        posts: Connection<String>,
+    }
+
+    #[test]
+    fn it_should_decode_connection() {
+        let data: Data = decode(r#"{"data":{"posts":{"edges":[{"node":"a"},{"node":"b"}],"pageInfo":{"hasNextPage":true,"endCursor":"b"}}}}"#).unwrap();
+
+        assert_eq!(data.posts.edges.len(), 2);
+        assert_eq!(data.posts.page_info.next_cursor(), Some("b"));
+    }
+
+    #[test]
+    fn it_should_return_error_response_errors() {
+        let result = decode::<Data>(r#"{"data":null,"errors":[{"message":"Unknown host"},{"message":"Rate limited"}]}"#);
+
+        let err = result.unwrap_err();
+        assert_eq!(err.message, "Unknown host; Rate limited");
+        assert!(err.body.is_some());
+        assert!(decode::<Data>("<html>Bad gateway</html>").is_err());
+    }
+}
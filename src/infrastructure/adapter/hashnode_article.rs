@@ -0,0 +1,268 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::json;
+use url::Url;
+
+use crate::domain::{article::{adapter::ArticleAdapter, model::ArticleCreateModel}, dead_letter::model::{FetchFailure, Fetched}, error::DomainError, registered_author::model::RegisteredAuthorModel};
+
+use super::{feed::parse_date, graphql::{Connection, GraphQlClient}};
+
+/// Public GraphQL API of Hashnode.
+pub const HASHNODE_API_URL: &str = "https://gql.hashnode.com";
+
+// Largest page of posts the API returns
+const PAGE_SIZE: i32 = 20;
+
+const QUERY_POSTS: &str = "
+    query Posts($host: String!, $first: Int!, $after: String) {
+        publication(host: $host) {
+            posts(first: $first, after: $after) {
+                edges {
+                    node {
+                        id
+                        title
+                        brief
+                        url
+                        publishedAt
+                        updatedAt
+                        readTimeInMinutes
+                        coverImage { url }
+                        tags { name }
+                    }
+                }
+                pageInfo { hasNextPage endCursor }
+            }
+        }
+    }";
+
+#[derive(Deserialize)]
+struct PostsData {
+    publication: Option<Publication>,
+}
+
+#[derive(Deserialize)]
+struct Publication {
+    posts: Connection<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HashnodePost {
+    id: String,
+    title: String,
+    brief: Option<String>,
+    url: String,
+    published_at: String,
+    updated_at: Option<String>,
+    read_time_in_minutes: i32,
+    cover_image: Option<CoverImage>,
+    tags: Option<Vec<Tag>>,
+}
+
+#[derive(Deserialize)]
+struct CoverImage {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct Tag {
+    name: String,
+}
+
+// The Hashnode CDN crops its images to the size asked, covers being 1600x840
+fn modify_hashnode_image_url(url_str: &str, width: u16) -> Result<String, url::ParseError> {
+    let mut url = Url::parse(url_str)?;
+
+    if url.host_str() != Some("cdn.hashnode.com") {
+        return Ok(url.to_string());
+    }
+
+    let height = u32::from(width) * 21 / 40;
+    url.set_query(Some(&format!("w={}&h={}&fit=crop&crop=entropy&auto=compress,format", width, height)));
+    Ok(url.to_string())
+}
+
+// Prefixed by the source, like the ids of the other APIs
+fn extid(source: &str, id: &str) -> String {
+    format!("{}{}", source, id)
+}
+
+fn variables(author: &RegisteredAuthorModel, after: Option<&str>) -> serde_json::Value {
+    json!({ "host": author.name, "first": PAGE_SIZE, "after": after })
+}
+
+// Maps one post, failing on a missing field or an invalid date
+fn to_article(value: &serde_json::Value, author: &RegisteredAuthorModel) -> Result<ArticleCreateModel, String> {
+    let post = HashnodePost::deserialize(value).map_err(|err| format!("Invalid post: {}", err))?;
+
+    let publish_at = parse_date(&post.published_at)?;
+    let edited_at = post.updated_at.as_deref().map(parse_date).transpose()?;
+    let (highres_link, photo_link, thumb_link) = match &post.cover_image {
+        Some(cover_image) => {
+            let image_link = |width| modify_hashnode_image_url(&cover_image.url, width)
+                .map_err(|err| format!("Invalid image link {}: {}", cover_image.url, err));
+            (Some(image_link(800)?), Some(image_link(600)?), Some(image_link(400)?))
+        }
+        None => (None, None, None),
+    };
+    let tags = post.tags.unwrap_or_default().into_iter().map(|tag| tag.name).collect();
+
+    Ok(ArticleCreateModel::new(
+        post.title,
+        post.brief.filter(|brief| !brief.is_empty()),
+        extid(&author.source, &post.id),
+        post.read_time_in_minutes,
+        author.source.clone(),
+        post.url,
+        author.name.clone(),
+        publish_at,
+        highres_link,
+        photo_link,
+        thumb_link,
+    )
+    .with_tags(tags)
+    .with_edited_at(edited_at))
+}
+
+/// Reads every post of a Hashnode publication, the author name being the
+/// publication host (`<blog>.hashnode.dev` or its custom domain).
+pub struct HashnodeArticleAdapter {
+    client: GraphQlClient,
+}
+
+impl HashnodeArticleAdapter {
+    pub fn new() -> Self {
+        Self {
+            client: GraphQlClient::new(HASHNODE_API_URL),
+        }
+    }
+
+    async fn fetch_page(&self, author: &RegisteredAuthorModel, after: Option<&str>) -> Result<Connection<serde_json::Value>, FetchFailure> {
+        let failure = |err: &dyn std::fmt::Display| FetchFailure::scope(&author.source, &author.name, err);
+
+        let data: PostsData = self.client.query(QUERY_POSTS, variables(author, after)).await.map_err(|err| {
+            let failure = failure(&format!("Failed to fetch posts: {}", err));
+            match &err.body {
+                Some(body) => failure.with_payload(body),
+                None => failure,
+            }
+        })?;
+
+        data.publication
+            .map(|publication| publication.posts)
+            .ok_or_else(|| failure(&"No Hashnode publication at this host"))
+    }
+}
+
+#[async_trait]
+impl ArticleAdapter for HashnodeArticleAdapter {
+    async fn fetch(&self, author: &RegisteredAuthorModel) -> Result<Fetched<ArticleCreateModel>, DomainError> {
+        let mut fetched = Fetched::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let posts = match self.fetch_page(author, after.as_deref()).await {
+                Ok(posts) => posts,
+                Err(failure) => {
+                    // The posts of the pages read still sync, the failure
+                    // keeps the author from being swept
+                    fetched.failures.push(failure);
+                    break;
+                }
+            };
+
+            for edge in &posts.edges {
+                match to_article(&edge.node, author) {
+                    Ok(article) => fetched.items.push(article),
+                    Err(err) => {
+                        let key = edge
+                            .node
+                            .get("id")
+                            .and_then(|id| id.as_str())
+                            .map_or_else(|| author.name.clone(), |id| extid(&author.source, id));
+                        fetched
+                            .failures
+                            .push(FetchFailure::new(&author.source, &author.name, &key, err).with_payload(&edge.node.to_string()));
+                    }
+                }
+            }
+
+            match posts.page_info.next_cursor() {
+                Some(cursor) => after = Some(cursor.to_string()),
+                None => break,
+            }
+        }
+
+        Ok(fetched)
+    }
+
+    fn lists_all(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POSTS: &str = include_str!("fixtures/hashnode_posts.json");
+
+    fn author() -> RegisteredAuthorModel {
+        let mut author = RegisteredAuthorModel::mock_default();
+        author.source = "hashnode".to_string();
+        author.name = "eng.datanorth.ca".to_string();
+        author
+    }
+
+    fn posts() -> Connection<serde_json::Value> {
+        let response: serde_json::Value = serde_json::from_str(POSTS).unwrap();
+        PostsData::deserialize(&response["data"]).unwrap().publication.unwrap().posts
+    }
+
+    fn articles() -> Vec<Result<ArticleCreateModel, String>> {
+        posts().edges.iter().map(|edge| to_article(&edge.node, &author())).collect()
+    }
+
+    #[test]
+    fn it_should_map_post_fields() {
+        let article = articles().remove(0).unwrap();
+
+        assert_eq!(article.name, "Streaming CDC into the warehouse");
+        assert_eq!(article.extid, "hashnode652557b1f0c3a2e5d1a4b9c7");
+        assert_eq!(article.link, "https://eng.datanorth.ca/streaming-cdc-into-the-warehouse");
+        assert_eq!(article.source, "hashnode");
+        assert_eq!(article.author, "eng.datanorth.ca");
+        assert_eq!(article.time_m, 11);
+        assert_eq!(article.tags, vec!["Data Engineering", "Rust"]);
+        assert!(article.description.unwrap().starts_with("We replaced nightly dumps"));
+        assert_eq!(article.publish_at.to_rfc3339(), "2023-10-10T13:00:45.120+00:00");
+        assert_eq!(article.edited_at.unwrap().to_rfc3339(), "2023-10-12T08:30:00+00:00");
+        assert_eq!(
+            article.photo_link.as_deref(),
+            Some("https://cdn.hashnode.com/res/hashnode/image/upload/v1696942840123/cdc-cover.png?w=600&h=315&fit=crop&crop=entropy&auto=compress,format")
+        );
+    }
+
+    #[test]
+    fn it_should_map_post_without_cover_or_update() {
+        let article = articles().remove(1).unwrap();
+
+        assert!(article.thumb_link.is_none());
+        assert!(article.edited_at.is_none());
+        assert!(article.tags.is_empty());
+    }
+
+    #[test]
+    fn it_should_return_error_invalid_post() {
+        assert!(articles().remove(2).is_err());
+    }
+
+    #[test]
+    fn it_should_page_posts_by_cursor() {
+        let posts = posts();
+        let cursor = posts.page_info.next_cursor();
+
+        assert_eq!(variables(&author(), None), json!({ "host": "eng.datanorth.ca", "first": 20, "after": null }));
+        assert_eq!(variables(&author(), cursor)["after"], json!(posts.page_info.end_cursor));
+    }
+}
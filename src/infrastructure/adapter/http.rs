@@ -0,0 +1,45 @@
+use std::time::Instant;
+
+use reqwest::{RequestBuilder, Response};
+use tracing::Instrument;
+
+use crate::infrastructure::metrics;
+
+/// Sends an upstream request in its own span, timing it by host and status
+/// code. Requests that fail before a response are recorded with the `error`
+/// status.
+pub async fn send(request: RequestBuilder) -> reqwest::Result<Response> {
+    let url = request
+        .try_clone()
+        .and_then(|request| request.build().ok())
+        .map(|request| request.url().clone());
+    let host = url
+        .as_ref()
+        .and_then(|url| url.host_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let span = tracing::info_span!(
+        "http_request",
+        host = %host,
+        url = url.as_ref().map(|url| url.as_str()).unwrap_or_default(),
+        status = tracing::field::Empty,
+    );
+
+    async move {
+        let started = Instant::now();
+        let response = request.send().await;
+        let status = match &response {
+            Ok(response) => response.status().as_str().to_string(),
+            Err(_) => "error".to_string(),
+        };
+
+        tracing::Span::current().record("status", status.as_str());
+        tracing::debug!(elapsed_ms = started.elapsed().as_millis() as u64, "HTTP request finished");
+        metrics::record_http_request(&host, &status, started.elapsed());
+
+        response
+    }
+    .instrument(span)
+    .await
+}
@@ -2,8 +2,9 @@ use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use async_trait::async_trait;
+use tracing::Instrument;
 
-use crate::domain::{event::{adapter::EventAdapter, model::{EventCreateModel}}, error::DomainError, group::model::GroupModel};
+use crate::domain::{dead_letter::model::{FetchFailure, Fetched}, event::{adapter::EventAdapter, model::{EventCreateModel}}, error::DomainError, group::model::GroupModel};
 use chrono::{NaiveDateTime};
 use serde::{ Serialize,Deserialize};
 use chrono::{ DateTime, Utc};
@@ -11,6 +12,8 @@ use chrono::format::ParseError;
 
 use super::meetup_group::{RateLimitedClient};
 
+const SOURCE: &str = "meetup";
+
 #[derive(Serialize,Deserialize)]
 struct Venue {
     id: i32,
@@ -77,67 +80,99 @@ impl MeetupEventAdapter {
             client,
         }
     }
+
+    async fn fetch_group_events(&self, group_model: &GroupModel) -> Result<Fetched<EventCreateModel>, FetchFailure> {
+        let slug = &group_model.slug;
+        let failure = |err: &dyn std::fmt::Display| FetchFailure::scope(SOURCE, slug, err);
+        let mut fetched = Fetched::new();
+
+        let url = format!("https://api.meetup.com/{}/events", slug);
+        let response = self.client.lock().await.get(&url).await.map_err(|err| failure(&err))?;
+        let success = response.status().is_success();
+        let body = response.text().await.map_err(|err| failure(&err))?;
+
+        if !success {
+            return Err(failure(&"Failed to fetch events from Meetup API").with_payload(&body));
+        }
+
+        // Decoded one by one so a malformed event doesn't fail its whole group
+        let values: Vec<serde_json::Value> = serde_json::from_str(&body)
+            .map_err(|err| failure(&format!("Invalid events: {}", err)).with_payload(&body))?;
+
+        for value in values {
+            match self.to_event(group_model, &value).await {
+                Ok(event) => fetched.items.push(event),
+                Err(err) => {
+                    let key = value
+                        .get("id")
+                        .and_then(|id| id.as_str())
+                        .map_or_else(|| slug.clone(), |id| format!("m{}", id));
+                    fetched
+                        .failures
+                        .push(FetchFailure::new(SOURCE, slug, &key, err).with_payload(&value.to_string()));
+                }
+            }
+        }
+
+        Ok(fetched)
+    }
+
+    async fn to_event(&self, group_model: &GroupModel, value: &serde_json::Value) -> Result<EventCreateModel, String> {
+        let meetup_event = MeetupEvent::deserialize(value).map_err(|err| format!("Invalid event: {}", err))?;
+
+        let url = format!("https://api.meetup.com/{}/events/{}/photos", &group_model.slug, &meetup_event.id);
+        let photo_response = self.client.lock().await.get(&url).await.map_err(|err| err.to_string())?;
+
+        let photo_album: Vec<MeetupGroupPhoto> = if photo_response.status().is_success() {
+            photo_response.json().await.map_err(|err| format!("Invalid photos: {}", err))?
+        } else {
+            vec![] // or provide a default
+        };
+
+        let (highres_link, thumb_link, photo_link) = if let Some(first_photo) = photo_album.first() {
+            (first_photo.highres_link.clone(), first_photo.thumb_link.clone(), first_photo.photo_link.clone())
+        } else {
+            (String::from(""), String::from(""), String::from("")) // or provide a default
+        };
+
+        let venue_name = meetup_event.venue.as_ref().map_or(String::from(""), |venue| venue.name.clone());
+        let time = convert_ms(meetup_event.time).map_err(|err| format!("Invalid date: {}", err))?;
+
+        Ok(EventCreateModel::new(
+            meetup_event.name,
+            meetup_event.description,
+            venue_name,
+            group_model.groupid,
+            format!("m{}", meetup_event.id),
+            meetup_event.link,
+            meetup_event.event_type == "PHYSICAL",
+            meetup_event.is_online_event,
+            meetup_event.duration,
+            meetup_event.waitlist_count,
+            meetup_event.yes_rsvp_count,
+            meetup_event.member_pay_fee,
+            meetup_event.rsvp_limit,
+            time,
+            Some(highres_link),
+            Some(photo_link),
+            Some(thumb_link),
+        )
+        .with_group_slug(&group_model.slug))
+    }
 }
 #[async_trait]
 impl EventAdapter for MeetupEventAdapter {
-    async fn fetch(&self, group_models: Vec<GroupModel>) -> Result<Vec<EventCreateModel>, DomainError> {
-        let mut events: Vec<EventCreateModel> = Vec::new();
+    async fn fetch(&self, group_models: Vec<GroupModel>) -> Result<Fetched<EventCreateModel>, DomainError> {
+        let mut fetched = Fetched::new();
 
         for group_model in group_models {
-            let url = format!("https://api.meetup.com/{}/events", &group_model.slug);
-            println!("{:?}",&url);
-            let response = self.client.lock().await.get(&url).await?;
-            if response.status().is_success() {
-                let resp: Vec<MeetupEvent> = response.json().await?;
-
-                for meetup_event in resp {
-                    let url = format!("https://api.meetup.com/{}/events/{}/photos", &group_model.slug,&meetup_event.id);
-                    let photo_response = self.client.lock().await.get(&url).await?;
-
-                    let photo_album: Vec<MeetupGroupPhoto> = if photo_response.status().is_success() {
-                        photo_response.json().await?
-                    } else {
-                        vec![] // or provide a default
-                    };
-
-                    let (highres_link, thumb_link, photo_link) = if let Some(first_photo) = photo_album.first() {
-                        (first_photo.highres_link.clone(), first_photo.thumb_link.clone(), first_photo.photo_link.clone())
-                    } else {
-                        (String::from(""), String::from(""), String::from("")) // or provide a default
-                    };
-
-                    let venue_name = meetup_event.venue.as_ref().map_or(String::from(""), |venue| venue.name.clone());
-
-                    events.push(
-                        EventCreateModel::new(
-                            meetup_event.name,
-                            meetup_event.description,
-                            venue_name,
-                            group_model.groupid.clone(),
-                            format!("m{}", meetup_event.id), 
-                            meetup_event.link,
-                            meetup_event.event_type == "PHYSICAL",
-                            meetup_event.is_online_event,
-                            meetup_event.duration, 
-                            meetup_event.waitlist_count, 
-                            meetup_event.yes_rsvp_count, 
-                            meetup_event.member_pay_fee, 
-                            meetup_event.rsvp_limit, 
-                            convert_ms(meetup_event.time).expect("Failed parse date"), 
-                            Some(highres_link), 
-                            Some(photo_link),
-                            Some(thumb_link),
-                            meetup_event.updated.to_string(),
-                        ),
-                    );
-                }
-
-            } else {
-                let error_message = response.text().await?;
-                return Err(DomainError::InternalServerError(format!("Failed to fetch from Meetup API: {}", error_message)));
+            let span = tracing::info_span!("group", source = SOURCE, group = %group_model.slug);
+            match self.fetch_group_events(&group_model).instrument(span).await {
+                Ok(events) => fetched.extend(events),
+                Err(failure) => fetched.failures.push(failure),
             }
         }
 
-        Ok(events)
+        Ok(fetched)
     }
 }
@@ -6,11 +6,14 @@ use tokio::sync::{Mutex};
 use async_trait::async_trait;
 use std::time::Duration;
 use crate::domain::city::repository::CityRepository;
+use crate::domain::dead_letter::model::{FetchFailure, Fetched};
 use crate::domain::error::DomainError;
+use crate::infrastructure::{adapter::http, metrics};
 use crate::domain::group::{adapter::GroupAdapter, model::{GroupCreateModel, ImageLinks}};
 use serde::{Serialize, Deserialize};
 use regex::Regex;
 use tokio::time::sleep;
+use tracing::Instrument;
 use reqwest::{Client, Response};
 
 pub struct RateLimitedClient {
@@ -34,7 +37,7 @@ impl RateLimitedClient {
 
     pub async fn get(&mut self, url: &str) -> Result<Response, DomainError> {
         self.check_rate_limit(url).await?;
-        let resp = self.client.get(url).send().await?;
+        let resp = http::send(self.client.get(url)).await?;
         self.update_rate_limit(url, &resp);
         Ok(resp)
     }
@@ -68,8 +71,11 @@ impl RateLimitedClient {
             .and_then(|val| val.to_str().ok())
             .and_then(|s| s.parse::<u64>().ok());
 
-        println!("{:?} {:?} {:?}",&limit,&remaining,&reset);
+        tracing::debug!(?limit, ?remaining, ?reset, "Rate limit");
         if let (Some(limit), Some(remaining), Some(reset)) = (limit, remaining, reset) {
+            if let Some(host) = resp.url().host_str() {
+                metrics::record_rate_limit(host, limit, remaining);
+            }
             self.rate_limits.insert(
                 url.to_string(),
                 RateLimit {
@@ -122,6 +128,8 @@ struct MeetupAPIGroupResponse {
     organizer: MeetupOrganizer,
 }
 
+const SOURCE: &str = "meetup";
+
 fn remove_html_tags(input: &str) -> String {
     let re = Regex::new(r"<[^>]+>").unwrap();
     re.replace_all(input, "").to_string()
@@ -143,50 +151,61 @@ impl MeetupGroupAdapter {
             city_repository
         }
     }
+
+    async fn fetch_group(&self, name: &str) -> Result<GroupCreateModel, FetchFailure> {
+        let failure = |err: &dyn std::fmt::Display| FetchFailure::scope(SOURCE, name, err);
+
+        let url = format!("https://api.meetup.com/{}", name);
+        let response = self.client.lock().await.get(&url).await.map_err(|err| failure(&err))?;
+        let success = response.status().is_success();
+        let body = response.text().await.map_err(|err| failure(&err))?;
+
+        if success {
+            let resp: MeetupAPIGroupResponse = serde_json::from_str(&body)
+                .map_err(|err| failure(&format!("Invalid group: {}", err)).with_payload(&body))?;
+            let description = remove_html_tags(&resp.description);
+
+            let photo_links = resp.key_photo.map(|photo| ImageLinks{
+                photo_link: Some(photo.photo_link),
+                thumb_link: Some(photo.thumb_link),
+                highres_link: Some(photo.highres_link),
+            }).unwrap_or_else(|| ImageLinks{
+                photo_link: None,
+                thumb_link: None,
+                highres_link: None,
+            });
+
+            Ok(GroupCreateModel::new(
+                resp.id.to_string(),
+                resp.name.clone(),
+                description,
+                resp.urlname,
+                resp.status == "active",
+                resp.join_mode != "open",
+                resp.members,
+                resp.organizer.name,
+                photo_links,
+                resp.city,
+            )
+            .with_registered_name(name))
+        } else {
+            Err(failure(&"Failed to fetch group from Meetup API").with_payload(&body))
+        }
+    }
 }
 #[async_trait]
 impl GroupAdapter for MeetupGroupAdapter {
-    async fn fetch(&self, names: Vec<String>) -> Result<Vec<GroupCreateModel>, DomainError> {
-        let mut groups: Vec<GroupCreateModel> = Vec::new();
+    async fn fetch(&self, names: Vec<String>) -> Result<Fetched<GroupCreateModel>, DomainError> {
+        let mut fetched = Fetched::new();
 
         for name in names {
-            let url = format!("https://api.meetup.com/{}", name);
-            println!("{}",&url);
-            let response = self.client.lock().await.get(&url).await?;
-
-            if response.status().is_success() {
-                let resp: MeetupAPIGroupResponse = response.json().await?;
-                let description = remove_html_tags(&resp.description);
-
-                let photo_links = resp.key_photo.map(|photo| ImageLinks{
-                    photo_link: Some(photo.photo_link),
-                    thumb_link: Some(photo.thumb_link),
-                    highres_link: Some(photo.highres_link),
-                }).unwrap_or_else(|| ImageLinks{
-                    photo_link: None,
-                    thumb_link: None,
-                    highres_link: None,
-                });
-
-                groups.push(
-                    GroupCreateModel::new(
-                        resp.id.to_string(),
-                        resp.name.clone(),
-                        description,
-                        resp.urlname,
-                        resp.status == "active",
-                        resp.join_mode != "open",
-                        resp.members,
-                        resp.organizer.name,
-                        photo_links,
-                        resp.city,
-                    ),
-                );
-            } else {
-                let error_message = response.text().await?;
-                return Err(DomainError::InternalServerError(format!("Failed to fetch from Meetup API: {}", error_message)));
+            let span = tracing::info_span!("group", source = SOURCE, group = %name);
+            match self.fetch_group(&name).instrument(span).await {
+                Ok(group) => fetched.items.push(group),
+                Err(failure) => fetched.failures.push(failure),
             }
         }
+        let groups = &mut fetched.items;
         let city_extids: HashSet<_> = groups.iter().map(|group| group.cityextid.clone()).collect();
         let city_extids_vec: Vec<_> = city_extids.into_iter().collect();
 
@@ -198,13 +217,13 @@ impl GroupAdapter for MeetupGroupAdapter {
                 .map(|model| (model.name, model.cityid))
                 .collect();
         
-            for group in &mut groups {
+            for group in groups.iter_mut() {
                 if let Some(cityid) = cityid_by_extid.get(&group.cityextid) {
                     group.set_cityid(*cityid);
                 }
             }
         }
 
-        Ok(groups)
+        Ok(fetched)
     }
 }
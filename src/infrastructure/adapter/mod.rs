@@ -1,3 +1,9 @@
-pub mod medium_article;
+pub mod http;
+pub mod feed;
+pub mod feed_article;
 pub mod meetup_group;
-pub mod meetup_event;
\ No newline at end of file
+pub mod meetup_event;
+pub mod substack_article;
+pub mod forem_article;
+pub mod graphql;
+pub mod hashnode_article;
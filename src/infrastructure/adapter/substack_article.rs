@@ -0,0 +1,168 @@
+use async_trait::async_trait;
+
+use crate::domain::{article::{adapter::ArticleAdapter, model::ArticleCreateModel}, dead_letter::model::Fetched, error::DomainError, registered_author::model::RegisteredAuthorModel};
+use url::form_urlencoded::byte_serialize;
+
+use super::{feed::FeedEntry, feed_article::{extract_image_link, fetch_feed, remove_html_tags, READING_TIME_MINUTES}};
+
+/// Feed of the Substack newsletters, `{name}` being the newsletter subdomain.
+pub const SUBSTACK_FEED_URL: &str = "https://{name}.substack.com/feed";
+
+// Resizes any image, the original URL being url encoded after the options
+const SUBSTACK_CDN_URL: &str = "https://substackcdn.com/image/fetch/";
+
+// Substack cuts the content of the paywalled posts at this element
+const PAYWALL_MARKER: &str = r#"class="paywall"#;
+
+fn cover_image_url(image: &str, width: u16) -> String {
+    let original = match image.strip_prefix(SUBSTACK_CDN_URL) {
+        Some(fetch) => fetch.split_once('/').map_or(fetch, |(_, original)| original).to_string(),
+        None => byte_serialize(image.as_bytes()).collect(),
+    };
+    format!("{}w_{},c_limit,f_auto,q_auto:good,fl_progressive:steep/{}", SUBSTACK_CDN_URL, width, original)
+}
+
+// Text of the post, only the preview before the paywall of the paid posts,
+// falling back on the subtitle when there is no preview
+fn description(entry: &FeedEntry) -> Option<String> {
+    let preview = entry.content.as_ref().map(|content| {
+        let free = content.find(PAYWALL_MARKER).map_or(content.as_str(), |paywall| {
+            // Back to the start of the element holding the marker
+            content[..paywall].rfind('<').map_or(&content[..paywall], |start| &content[..start])
+        });
+        remove_html_tags(free).trim().to_string()
+    });
+    preview.filter(|preview| !preview.is_empty()).or_else(|| entry.summary.clone())
+}
+
+// Maps one post, failing on a missing title, link or date
+fn to_article(entry: &FeedEntry, author: &RegisteredAuthorModel) -> Result<ArticleCreateModel, String> {
+    let link = entry.link.clone().ok_or("Missing link")?;
+    let extid = entry.id.clone().unwrap_or_else(|| link.clone());
+    let title = entry.title.clone().ok_or("Missing title")?;
+    let publish_at = entry.publish_at()?;
+
+    let image = entry.image.clone().or_else(|| entry.content.as_ref().and_then(|content| extract_image_link(content)));
+    let image_link = |width| image.as_ref().map(|image| cover_image_url(image, width));
+
+    Ok(ArticleCreateModel::new(
+        title,
+        description(entry),
+        extid,
+        READING_TIME_MINUTES,
+        author.source.clone(),
+        link,
+        author.name.clone(),
+        publish_at,
+        image_link(800),
+        image_link(600),
+        image_link(400),
+    ))
+}
+
+/// Reads the posts of a Substack newsletter, at the feed URL registered with
+/// the author for the custom domains.
+pub struct SubstackArticleAdapter {
+    client: reqwest::Client,
+}
+
+impl SubstackArticleAdapter {
+    pub fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn feed_url(author: &RegisteredAuthorModel) -> String {
+        author
+            .feed_url
+            .clone()
+            .unwrap_or_else(|| SUBSTACK_FEED_URL.replace("{name}", &author.name))
+    }
+}
+
+#[async_trait]
+impl ArticleAdapter for SubstackArticleAdapter {
+    async fn fetch(&self, author: &RegisteredAuthorModel) -> Result<Fetched<ArticleCreateModel>, DomainError> {
+        fetch_feed(&self.client, &Self::feed_url(author), author, to_article).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::infrastructure::adapter::feed;
+
+    const FEED: &str = include_str!("fixtures/substack_feed.xml");
+
+    fn author() -> RegisteredAuthorModel {
+        let mut author = RegisteredAuthorModel::mock_default();
+        author.source = "substack".to_string();
+        author.name = "datanorth".to_string();
+        author
+    }
+
+    fn articles() -> Vec<Result<ArticleCreateModel, String>> {
+        feed::parse(FEED).unwrap().iter().map(|entry| to_article(entry, &author())).collect()
+    }
+
+    #[test]
+    fn it_should_map_free_post() {
+        let article = articles().remove(0).unwrap();
+
+        assert_eq!(article.name, "Open data in Montréal, one year later");
+        assert_eq!(article.extid, "https://datanorth.substack.com/p/open-data-in-montreal");
+        assert_eq!(article.link, "https://datanorth.substack.com/p/open-data-in-montreal");
+        assert_eq!(article.source, "substack");
+        assert_eq!(article.author, "datanorth");
+        assert_eq!(article.publish_at.to_rfc3339(), "2023-10-10T13:00:45+00:00");
+        assert_eq!(
+            article.description.as_deref(),
+            Some("A year ago the city opened its transit feeds.Here is what people built with them.")
+        );
+    }
+
+    #[test]
+    fn it_should_map_cover_image_sizes() {
+        let articles = articles();
+        let free = articles[0].as_ref().unwrap();
+        let paid = articles[1].as_ref().unwrap();
+
+        assert_eq!(
+            free.highres_link.as_deref(),
+            Some("https://substackcdn.com/image/fetch/w_800,c_limit,f_auto,q_auto:good,fl_progressive:steep/https%3A%2F%2Fsubstack-post-media.s3.amazonaws.com%2Fpublic%2Fimages%2F5b1c7e2a-cover_1600x900.png")
+        );
+        assert_eq!(
+            paid.thumb_link.as_deref(),
+            Some("https://substackcdn.com/image/fetch/w_400,c_limit,f_auto,q_auto:good,fl_progressive:steep/https%3A%2F%2Fsubstack-post-media.s3.amazonaws.com%2Fpublic%2Fimages%2Fsalaries.png")
+        );
+        assert!(articles[2].as_ref().unwrap().photo_link.is_none());
+    }
+
+    #[test]
+    fn it_should_keep_preview_of_paywalled_posts() {
+        let articles = articles();
+
+        assert_eq!(articles[1].as_ref().unwrap().description.as_deref(), Some("More than 400 of you answered the survey."));
+        assert_eq!(articles[2].as_ref().unwrap().description.as_deref(), Some("The conventions we settled on"));
+    }
+
+    #[test]
+    fn it_should_return_error_post_without_date() {
+        let articles = articles();
+
+        assert_eq!(articles.len(), 4);
+        assert!(articles[3].is_err());
+    }
+
+    #[test]
+    fn it_should_prefer_registered_feed_url() {
+        let mut author = author();
+
+        assert_eq!(SubstackArticleAdapter::feed_url(&author), "https://datanorth.substack.com/feed");
+
+        author.feed_url = Some("https://news.example.ca/feed".to_string());
+        assert_eq!(SubstackArticleAdapter::feed_url(&author), "https://news.example.ca/feed");
+    }
+}
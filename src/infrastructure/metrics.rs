@@ -0,0 +1,192 @@
+use std::time::Duration;
+
+use deadpool_postgres::Pool;
+use lazy_static::lazy_static;
+use prometheus::{
+    core::{Collector, Desc},
+    proto::MetricFamily,
+    register, register_histogram_vec, register_int_counter_vec, register_int_gauge_vec,
+    Encoder, HistogramVec, IntCounterVec, IntGaugeVec, Opts, TextEncoder,
+};
+
+use crate::domain::{error::DomainError, sync_run::model::SyncReport};
+
+lazy_static! {
+    static ref SYNC_ITEMS: IntCounterVec = register_int_counter_vec!(
+        "sync_items_total",
+        "Items fetched from a source and how they were written",
+        &["job", "source", "outcome"]
+    )
+    .expect("sync_items_total is registered once");
+    static ref SYNC_DELETED_ITEMS: IntCounterVec = register_int_counter_vec!(
+        "sync_deleted_items_total",
+        "Items deleted after missing from their source for too many runs",
+        &["job"]
+    )
+    .expect("sync_deleted_items_total is registered once");
+    static ref HTTP_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "sync_http_request_duration_seconds",
+        "Upstream HTTP request latency by host and status code",
+        &["host", "status"]
+    )
+    .expect("sync_http_request_duration_seconds is registered once");
+    static ref RATE_LIMIT: IntGaugeVec = register_int_gauge_vec!(
+        "sync_rate_limit",
+        "Last rate limit announced by an upstream API",
+        &["host", "kind"]
+    )
+    .expect("sync_rate_limit is registered once");
+}
+
+/// Counts the items of one fetched batch, `fetched` being all of them.
+pub fn record_items(job: &str, source: &str, fetched: usize, report: &SyncReport) {
+    let outcomes = [
+        ("fetched", fetched as u64),
+        ("inserted", report.inserted as u64),
+        ("updated", report.updated as u64),
+        ("unchanged", report.unchanged as u64),
+    ];
+    for (outcome, count) in outcomes {
+        SYNC_ITEMS
+            .with_label_values(&[job, source, outcome])
+            .inc_by(count);
+    }
+}
+
+/// Counts the items a batch of `source` failed to fetch or map.
+pub fn record_failed(job: &str, source: &str, failed: usize) {
+    SYNC_ITEMS
+        .with_label_values(&[job, source, "failed"])
+        .inc_by(failed as u64);
+}
+
+/// Counts the items a job deleted at the end of a complete run.
+pub fn record_deleted(job: &str, deleted: i32) {
+    SYNC_DELETED_ITEMS
+        .with_label_values(&[job])
+        .inc_by(deleted as u64);
+}
+
+/// Times an upstream request by host and status code.
+pub fn record_http_request(host: &str, status: &str, elapsed: Duration) {
+    HTTP_REQUEST_DURATION
+        .with_label_values(&[host, status])
+        .observe(elapsed.as_secs_f64());
+}
+
+/// Records the `x-ratelimit-limit` and `x-ratelimit-remaining` headers of a host.
+pub fn record_rate_limit(host: &str, limit: u32, remaining: u32) {
+    RATE_LIMIT
+        .with_label_values(&[host, "limit"])
+        .set(limit as i64);
+    RATE_LIMIT
+        .with_label_values(&[host, "remaining"])
+        .set(remaining as i64);
+}
+
+/// Reports the connections of the database pools, read on every scrape.
+pub fn register_pools(pools: Vec<(&'static str, Pool)>) -> Result<(), DomainError> {
+    register(Box::new(PoolCollector::new(pools)?))
+        .map_err(|err| DomainError::InternalServerError(err.to_string()))
+}
+
+/// Metrics in the Prometheus text format.
+pub fn encode() -> Result<String, DomainError> {
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .map_err(|err| DomainError::InternalServerError(err.to_string()))?;
+
+    String::from_utf8(buffer).map_err(|err| DomainError::InternalServerError(err.to_string()))
+}
+
+struct PoolCollector {
+    pools: Vec<(&'static str, Pool)>,
+    connections: IntGaugeVec,
+    max_connections: IntGaugeVec,
+    waiting: IntGaugeVec,
+}
+
+impl PoolCollector {
+    fn new(pools: Vec<(&'static str, Pool)>) -> Result<Self, DomainError> {
+        let gauge = |name: &str, help: &str, labels: &[&str]| {
+            IntGaugeVec::new(Opts::new(name, help), labels)
+                .map_err(|err| DomainError::InternalServerError(err.to_string()))
+        };
+
+        Ok(Self {
+            pools,
+            connections: gauge("sync_db_pool_connections", "Open connections of a database pool", &["pool", "state"])?,
+            max_connections: gauge("sync_db_pool_max_connections", "Maximum size of a database pool", &["pool"])?,
+            waiting: gauge("sync_db_pool_waiting", "Tasks waiting for a connection of a database pool", &["pool"])?,
+        })
+    }
+}
+
+impl Collector for PoolCollector {
+    fn desc(&self) -> Vec<&Desc> {
+        [&self.connections, &self.max_connections, &self.waiting]
+            .into_iter()
+            .flat_map(|gauge| gauge.desc())
+            .collect()
+    }
+
+    fn collect(&self) -> Vec<MetricFamily> {
+        for (name, pool) in &self.pools {
+            let status = pool.status();
+            let idle = status.available.max(0) as usize;
+
+            self.connections
+                .with_label_values(&[name, "idle"])
+                .set(idle as i64);
+            self.connections
+                .with_label_values(&[name, "in_use"])
+                .set(status.size.saturating_sub(idle) as i64);
+            self.max_connections
+                .with_label_values(&[name])
+                .set(status.max_size as i64);
+            self.waiting
+                .with_label_values(&[name])
+                .set((-status.available).max(0) as i64);
+        }
+
+        [&self.connections, &self.max_connections, &self.waiting]
+            .into_iter()
+            .flat_map(|gauge| gauge.collect())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_should_count_items_by_outcome() {
+        record_items(
+            "metrics_test",
+            "medium",
+            5,
+            &SyncReport {
+                inserted: 2,
+                updated: 1,
+                unchanged: 2,
+                deleted: 0,
+            },
+        );
+
+        let metrics = encode().unwrap();
+
+        assert!(metrics.contains(r#"sync_items_total{job="metrics_test",outcome="fetched",source="medium"} 5"#));
+        assert!(metrics.contains(r#"sync_items_total{job="metrics_test",outcome="inserted",source="medium"} 2"#));
+    }
+
+    #[test]
+    fn it_should_record_rate_limit() {
+        record_rate_limit("rate.limit.test", 30, 12);
+
+        let metrics = encode().unwrap();
+
+        assert!(metrics.contains(r#"sync_rate_limit{host="rate.limit.test",kind="remaining"} 12"#));
+    }
+}
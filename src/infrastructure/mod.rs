@@ -1,2 +1,4 @@
 pub mod repository;
-pub mod adapter;
\ No newline at end of file
+pub mod adapter;
+pub mod metrics;
+pub mod telemetry;
\ No newline at end of file
@@ -77,10 +77,25 @@ const QUERY_FIND_ARTICLE_BY_EXTID: &str = "
     where 
         extid = $1;";
 
+// Upsert, so an article written by a run that then failed to record it in the
+// sync database is overwritten by the next run
 const QUERY_INSERT_ARTICLE: &str = "
     insert into article(extid,name,description,time_m,source,link,author,highres_link,photo_link,thumb_link,publish_at)
     values
         ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11)
+    on conflict (extid) do update
+    set
+        name=excluded.name,
+        description=excluded.description,
+        time_m=excluded.time_m,
+        source=excluded.source,
+        link=excluded.link,
+        author=excluded.author,
+        highres_link=excluded.highres_link,
+        photo_link=excluded.photo_link,
+        thumb_link=excluded.thumb_link,
+        publish_at=excluded.publish_at,
+        updated_at=now()
     returning
         articleid,
         extid,
@@ -95,10 +95,31 @@ const QUERY_FIND_EVENT_BY_EXTID: &str = "
         where 
             extid = $1;";
 
+// Upsert, so an event written by a run that then failed to record it in the
+// sync database is overwritten by the next run
 const QUERY_INSERT_EVENT: &str = "
     insert into event(name,description,extid,location,groupid,in_person,time,duration,link,waitlist_count,is_online,yes_rsvp_count,fee,highres_link,photo_link,thumb_link,rsvp_limit)
     values
         ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17)
+    on conflict (extid) do update
+    set
+        name=excluded.name,
+        description=excluded.description,
+        location=excluded.location,
+        groupid=excluded.groupid,
+        in_person=excluded.in_person,
+        time=excluded.time,
+        duration=excluded.duration,
+        link=excluded.link,
+        waitlist_count=excluded.waitlist_count,
+        is_online=excluded.is_online,
+        yes_rsvp_count=excluded.yes_rsvp_count,
+        fee=excluded.fee,
+        highres_link=excluded.highres_link,
+        photo_link=excluded.photo_link,
+        thumb_link=excluded.thumb_link,
+        rsvp_limit=excluded.rsvp_limit,
+        updated_at=now()
     returning
         eventid,
         name,
@@ -80,10 +80,26 @@ from
 where 
     extid = $1;";
 
+// Upsert, so a group written by a run that then failed to record it in the
+// sync database is overwritten by the next run
 const QUERY_INSERT_GROUP: &str = "
     insert into \"group\"(name,description,extid,slug,private,members,cityid,organizer,highres_link,photo_link,thumb_link,active)
     values
         ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12)
+    on conflict (extid) do update
+    set
+        name=excluded.name,
+        description=excluded.description,
+        slug=excluded.slug,
+        private=excluded.private,
+        members=excluded.members,
+        cityid=excluded.cityid,
+        organizer=excluded.organizer,
+        highres_link=excluded.highres_link,
+        photo_link=excluded.photo_link,
+        thumb_link=excluded.thumb_link,
+        active=excluded.active,
+        updated_at=now()
     returning
         groupid,
         name,
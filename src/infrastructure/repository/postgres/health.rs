@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+
+use crate::domain::{error::DomainError, health::repository::HealthRepository};
+
+const QUERY_PING: &str = "select 1;";
+
+/// Health of a pool, used for the main and the sync database alike.
+pub struct PgHealthRepository {
+    pool: Arc<Pool>,
+}
+impl PgHealthRepository {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl HealthRepository for PgHealthRepository {
+    async fn ping(&self) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        client.simple_query(QUERY_PING).await?;
+        Ok(())
+    }
+}
@@ -3,4 +3,5 @@ pub mod article;
 pub mod city;
 pub mod group;
 pub mod event;
+pub mod health;
 pub mod postgres;
\ No newline at end of file
@@ -79,7 +79,7 @@ pub async fn run_migrations() -> Result<(), Box<dyn Error>> {
         .await?;
 
     for migration in migration_report.applied_migrations() {
-        println!(
+        tracing::info!(
             "Migration Applied -  Name: {}, Version: {}",
             migration.name(),
             migration.version()
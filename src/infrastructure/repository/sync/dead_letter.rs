@@ -0,0 +1,193 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+
+use tokio_postgres::{types::ToSql, Row};
+
+use crate::domain::{
+    dead_letter::{
+        model::{DeadLetterCreateModel, DeadLetterModel},
+        repository::DeadLetterRepository,
+    },
+    error::DomainError,
+};
+
+const QUERY_FIND_DEAD_LETTER: &str = "
+    select
+        dl.sync_dead_letterid,
+        dl.job,
+        dl.source,
+        dl.scope,
+        dl.key,
+        dl.payload,
+        dl.error,
+        dl.attempts,
+        dl.created_at,
+        dl.updated_at,
+        count(1) over ()::OID as count
+    from
+        sync_dead_letter dl";
+
+const QUERY_FIND_DEAD_LETTER_BY_ID: &str = "
+    select
+        dl.sync_dead_letterid,
+        dl.job,
+        dl.source,
+        dl.scope,
+        dl.key,
+        dl.payload,
+        dl.error,
+        dl.attempts,
+        dl.created_at,
+        dl.updated_at
+    from
+        sync_dead_letter dl
+    where
+        sync_dead_letterid = $1;";
+
+const QUERY_UPSERT_DEAD_LETTER: &str = "
+    insert into sync_dead_letter(job,source,scope,key,payload,error)
+    values
+        ($1,$2,$3,$4,$5,$6)
+    on conflict (job,source,key) do update
+    set
+        scope=excluded.scope,
+        payload=excluded.payload,
+        error=excluded.error,
+        attempts=sync_dead_letter.attempts+1,
+        updated_at=now()
+    returning
+        sync_dead_letterid,
+        job,
+        source,
+        scope,
+        key,
+        payload,
+        error,
+        attempts,
+        created_at,
+        updated_at;";
+
+const QUERY_DELETE_RESOLVED_DEAD_LETTERS: &str = "
+    delete from
+        sync_dead_letter
+    where
+        job = $1
+        and source = $2
+        and scope = any($3)
+        and not key = any($4);";
+
+pub struct PgDeadLetterRepository {
+    pool: Arc<Pool>,
+}
+impl PgDeadLetterRepository {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl DeadLetterRepository for PgDeadLetterRepository {
+    async fn find(
+        &self,
+        job: &Option<String>,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<DeadLetterModel>, u32)>, DomainError> {
+        let client = self.pool.get().await?;
+
+        let mut queries: Vec<String> = vec![];
+        let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+
+        if let Some(job) = job {
+            queries.push(format!("dl.job = ${}", params.len() + 1));
+            params.push(job);
+        }
+
+        let mut query = String::from(QUERY_FIND_DEAD_LETTER);
+        if !queries.is_empty() {
+            query = format!("{} where {}", query, queries.join(" and "));
+        }
+
+        let offset = page_size * (page - 1);
+        query = format!("{query} order by dl.sync_dead_letterid limit {page_size} offset {offset}");
+
+        let stmt = client.prepare(&query).await?;
+        let result = client.query(&stmt, &params[..]).await?;
+
+        if !result.is_empty() {
+            let count: u32 = result.first().unwrap().get("count");
+
+            let items: Vec<DeadLetterModel> = result.iter().map(|row| row.into()).collect();
+
+            return Ok(Some((items, count)));
+        }
+
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, id: &i32) -> Result<Option<DeadLetterModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_DEAD_LETTER_BY_ID).await?;
+
+        if let Some(result) = client.query_opt(&stmt, &[id]).await? {
+            return Ok(Some((&result).into()));
+        }
+
+        Ok(None)
+    }
+
+    async fn upsert(
+        &self,
+        dead_letter_create_model: &DeadLetterCreateModel,
+    ) -> Result<DeadLetterModel, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_UPSERT_DEAD_LETTER).await?;
+        let result = &client
+            .query_one(
+                &stmt,
+                &[
+                    &dead_letter_create_model.job,
+                    &dead_letter_create_model.source,
+                    &dead_letter_create_model.scope,
+                    &dead_letter_create_model.key,
+                    &dead_letter_create_model.payload,
+                    &dead_letter_create_model.error,
+                ],
+            )
+            .await?;
+
+        Ok(result.into())
+    }
+
+    async fn delete_resolved(
+        &self,
+        job: &str,
+        source: &str,
+        scopes: &[String],
+        keys: &[String],
+    ) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_DELETE_RESOLVED_DEAD_LETTERS).await?;
+        client.execute(&stmt, &[&job, &source, &scopes, &keys]).await?;
+        Ok(())
+    }
+}
+
+impl From<&Row> for DeadLetterModel {
+    fn from(row: &Row) -> Self {
+        Self {
+            sync_dead_letterid: row.get("sync_dead_letterid"),
+            job: row.get("job"),
+            source: row.get("source"),
+            scope: row.get("scope"),
+            key: row.get("key"),
+            payload: row.get("payload"),
+            error: row.get("error"),
+            attempts: row.get("attempts"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use deadpool_postgres::Pool;
 
 use tokio_postgres::{types::ToSql, Row};
@@ -19,24 +20,67 @@ impl PgDiffArticleRepository {
 const QUERY_FIND_ARTICLE: &str = "
     select
         key,
-        value
+        value,
+        tags,
+        edited_at,
+        publish_at
     from
         diff_article";
 
 
 const QUERY_INSERT_ARTICLE: &str = "
-        insert into diff_article(key,value)
+        insert into diff_article(key,value,tags,edited_at,publish_at)
         values
-            ($1,$2);";
+            ($1,$2,$3,$4,$5)
+        on conflict (key) do update
+        set
+            value=excluded.value,
+            tags=excluded.tags,
+            edited_at=excluded.edited_at,
+            publish_at=excluded.publish_at;";
     
 const QUERY_UPDATE_ARTICLE_BY_KEY: &str = "
         update
             diff_article 
         set
-            \"value\"=$2
+            \"value\"=$2,
+            tags=$3,
+            edited_at=$4,
+            publish_at=$5
         where
             \"key\" = $1;";
 
+const QUERY_TOUCH_ARTICLES: &str = "
+        update
+            diff_article
+        set
+            source=$2,
+            scope=$3,
+            last_seen_run=$4,
+            missed_runs=0
+        where
+            \"key\" = any($1);";
+
+const QUERY_MARK_UNSEEN_ARTICLES: &str = "
+        update
+            diff_article
+        set
+            missed_runs=missed_runs+1
+        where
+            source = $2
+            and scope = any($3)
+            and ($4::timestamptz is null or publish_at >= $4)
+            and last_seen_run is distinct from $1
+        returning
+            \"key\",
+            missed_runs;";
+
+const QUERY_DELETE_ARTICLES_BY_KEYS: &str = "
+        delete from
+            diff_article
+        where
+            \"key\" = any($1);";
+
 impl DiffArticleRepository for PgDiffArticleRepository {
     
 }
@@ -68,6 +112,37 @@ impl DiffOperations<DiffArticleModel> for PgDiffArticleRepository {
 
         return Ok(diff_articles);
     }
+
+    async fn touch_many(&self, extids: Vec<String>, source: &str, scope: &str, run_id: i32) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_TOUCH_ARTICLES).await?;
+        client.execute(&stmt, &[&extids, &source, &scope, &run_id]).await?;
+
+        Ok(())
+    }
+
+    async fn mark_unseen(&self, run_id: i32, source: &str, scopes: Vec<String>, since: Option<DateTime<Utc>>, missed_runs: i32) -> Result<Vec<String>, DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_MARK_UNSEEN_ARTICLES).await?;
+        let result = client.query(&stmt, &[&run_id, &source, &scopes, &since]).await?;
+
+        Ok(result
+            .iter()
+            .filter(|row| row.get::<_, i32>("missed_runs") >= missed_runs)
+            .map(|row| row.get("key"))
+            .collect())
+    }
+
+    async fn delete_by_extids(&self, extids: Vec<String>) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_DELETE_ARTICLES_BY_KEYS).await?;
+        client.execute(&stmt, &[&extids]).await?;
+
+        Ok(())
+    }
 }
 #[async_trait]
 impl BatchOperations<DiffArticleModel,DiffArticleModel,DiffArticleModel> for PgDiffArticleRepository {
@@ -79,6 +154,9 @@ impl BatchOperations<DiffArticleModel,DiffArticleModel,DiffArticleModel> for PgD
             client.execute(&stmt, &[
                 &article.key,
                 &article.value,
+                &article.tags,
+                &article.edited_at,
+                &article.publish_at,
             ]).await?;
         }
     
@@ -93,6 +171,9 @@ impl BatchOperations<DiffArticleModel,DiffArticleModel,DiffArticleModel> for PgD
             client.execute(&stmt, &[
                 &article.key,
                 &article.value,
+                &article.tags,
+                &article.edited_at,
+                &article.publish_at,
             ]).await?;
         }
     
@@ -106,6 +187,9 @@ impl From<&Row> for DiffArticleModel {
         Self {
             key: row.get("key"),
             value: row.get("value"),
+            tags: row.get("tags"),
+            edited_at: row.get("edited_at"),
+            publish_at: row.get("publish_at"),
         }
     }
 }
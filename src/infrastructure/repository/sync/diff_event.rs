@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use deadpool_postgres::Pool;
 
 use tokio_postgres::{types::ToSql, Row};
@@ -19,24 +20,61 @@ impl PgDiffEventRepository {
 const QUERY_FIND_EVENT: &str = "
     select
         key,
-        value
+        value,
+        \"time\"
     from
         diff_event";
 
 
 const QUERY_INSERT_EVENT: &str = "
-        insert into diff_event(key,value)
+        insert into diff_event(key,value,\"time\")
         values
-            ($1,$2);";
+            ($1,$2,$3)
+        on conflict (key) do update
+        set
+            value=excluded.value,
+            \"time\"=excluded.\"time\";";
     
 const QUERY_UPDATE_EVENT_BY_KEY: &str = "
         update
             diff_event 
         set
-            \"value\"=$2
+            \"value\"=$2,
+            \"time\"=$3
         where
             \"key\" = $1;";
 
+const QUERY_TOUCH_EVENTS: &str = "
+        update
+            diff_event
+        set
+            source=$2,
+            scope=$3,
+            last_seen_run=$4,
+            missed_runs=0
+        where
+            \"key\" = any($1);";
+
+const QUERY_MARK_UNSEEN_EVENTS: &str = "
+        update
+            diff_event
+        set
+            missed_runs=missed_runs+1
+        where
+            source = $2
+            and scope = any($3)
+            and ($4::timestamptz is null or \"time\" >= $4)
+            and last_seen_run is distinct from $1
+        returning
+            \"key\",
+            missed_runs;";
+
+const QUERY_DELETE_EVENTS_BY_KEYS: &str = "
+        delete from
+            diff_event
+        where
+            \"key\" = any($1);";
+
 impl DiffEventRepository for PgDiffEventRepository {
     
 }
@@ -68,6 +106,37 @@ impl DiffOperations<DiffEventModel> for PgDiffEventRepository {
 
         return Ok(diff_events);
     }
+
+    async fn touch_many(&self, extids: Vec<String>, source: &str, scope: &str, run_id: i32) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_TOUCH_EVENTS).await?;
+        client.execute(&stmt, &[&extids, &source, &scope, &run_id]).await?;
+
+        Ok(())
+    }
+
+    async fn mark_unseen(&self, run_id: i32, source: &str, scopes: Vec<String>, since: Option<DateTime<Utc>>, missed_runs: i32) -> Result<Vec<String>, DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_MARK_UNSEEN_EVENTS).await?;
+        let result = client.query(&stmt, &[&run_id, &source, &scopes, &since]).await?;
+
+        Ok(result
+            .iter()
+            .filter(|row| row.get::<_, i32>("missed_runs") >= missed_runs)
+            .map(|row| row.get("key"))
+            .collect())
+    }
+
+    async fn delete_by_extids(&self, extids: Vec<String>) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_DELETE_EVENTS_BY_KEYS).await?;
+        client.execute(&stmt, &[&extids]).await?;
+
+        Ok(())
+    }
 }
 #[async_trait]
 impl BatchOperations<DiffEventModel,DiffEventModel,DiffEventModel> for PgDiffEventRepository {
@@ -79,6 +148,7 @@ impl BatchOperations<DiffEventModel,DiffEventModel,DiffEventModel> for PgDiffEve
             client.execute(&stmt, &[
                 &article.key,
                 &article.value,
+                &article.time,
             ]).await?;
         }
     
@@ -93,6 +163,7 @@ impl BatchOperations<DiffEventModel,DiffEventModel,DiffEventModel> for PgDiffEve
             client.execute(&stmt, &[
                 &article.key,
                 &article.value,
+                &article.time,
             ]).await?;
         }
     
@@ -106,6 +177,7 @@ impl From<&Row> for DiffEventModel {
         Self {
             key: row.get("key"),
             value: row.get("value"),
+            time: row.get("time"),
         }
     }
 }
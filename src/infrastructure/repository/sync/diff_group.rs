@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use deadpool_postgres::Pool;
 
 use tokio_postgres::{types::ToSql, Row};
@@ -27,7 +28,10 @@ const QUERY_FIND_GROUP: &str = "
 const QUERY_INSERT_GROUP: &str = "
         insert into diff_group(key,value)
         values
-            ($1,$2);";
+            ($1,$2)
+        on conflict (key) do update
+        set
+            value=excluded.value;";
     
 const QUERY_UPDATE_GROUP_BY_KEY: &str = "
         update
@@ -37,6 +41,36 @@ const QUERY_UPDATE_GROUP_BY_KEY: &str = "
         where
             \"key\" = $1;";
 
+const QUERY_TOUCH_GROUPS: &str = "
+        update
+            diff_group
+        set
+            source=$2,
+            scope=$3,
+            last_seen_run=$4,
+            missed_runs=0
+        where
+            \"key\" = any($1);";
+
+const QUERY_MARK_UNSEEN_GROUPS: &str = "
+        update
+            diff_group
+        set
+            missed_runs=missed_runs+1
+        where
+            source = $2
+            and scope = any($3)
+            and last_seen_run is distinct from $1
+        returning
+            \"key\",
+            missed_runs;";
+
+const QUERY_DELETE_GROUPS_BY_KEYS: &str = "
+        delete from
+            diff_group
+        where
+            \"key\" = any($1);";
+
 impl DiffGroupRepository for PgDiffGroupRepository {
     
 }
@@ -68,6 +102,38 @@ impl DiffOperations<DiffGroupModel> for PgDiffGroupRepository {
 
         return Ok(diff_groups);
     }
+
+    async fn touch_many(&self, extids: Vec<String>, source: &str, scope: &str, run_id: i32) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_TOUCH_GROUPS).await?;
+        client.execute(&stmt, &[&extids, &source, &scope, &run_id]).await?;
+
+        Ok(())
+    }
+
+    async fn mark_unseen(&self, run_id: i32, source: &str, scopes: Vec<String>, _since: Option<DateTime<Utc>>, missed_runs: i32) -> Result<Vec<String>, DomainError> {
+        let client = self.pool.get().await?;
+
+        // Groups are not dated, their source lists every one
+        let stmt = client.prepare(QUERY_MARK_UNSEEN_GROUPS).await?;
+        let result = client.query(&stmt, &[&run_id, &source, &scopes]).await?;
+
+        Ok(result
+            .iter()
+            .filter(|row| row.get::<_, i32>("missed_runs") >= missed_runs)
+            .map(|row| row.get("key"))
+            .collect())
+    }
+
+    async fn delete_by_extids(&self, extids: Vec<String>) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_DELETE_GROUPS_BY_KEYS).await?;
+        client.execute(&stmt, &[&extids]).await?;
+
+        Ok(())
+    }
 }
 #[async_trait]
 impl BatchOperations<DiffGroupModel,DiffGroupModel,DiffGroupModel> for PgDiffGroupRepository {
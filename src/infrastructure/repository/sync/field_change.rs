@@ -0,0 +1,86 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+
+use tokio_postgres::Row;
+
+use crate::domain::{
+    error::DomainError,
+    field_change::{
+        model::{FieldChangeCreateModel, FieldChangeModel},
+        repository::FieldChangeRepository,
+    },
+};
+
+const QUERY_FIND_FIELD_CHANGE_BY_EXTID: &str = "
+    select
+        fc.sync_field_changeid,
+        fc.field,
+        fc.before,
+        fc.after,
+        fc.run_id,
+        fc.changed_at
+    from
+        sync_field_change fc
+    where
+        fc.entity = $1
+        and fc.extid = $2
+    order by
+        fc.sync_field_changeid;";
+
+const QUERY_INSERT_FIELD_CHANGE: &str = "
+    insert into sync_field_change(entity,extid,field,before,after,run_id)
+    values
+        ($1,$2,$3,$4,$5,$6);";
+
+pub struct PgFieldChangeRepository {
+    pool: Arc<Pool>,
+}
+impl PgFieldChangeRepository {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FieldChangeRepository for PgFieldChangeRepository {
+    async fn find_by_extid(&self, entity: &str, extid: &str) -> Result<Vec<FieldChangeModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_FIELD_CHANGE_BY_EXTID).await?;
+        let result = client.query(&stmt, &[&entity, &extid]).await?;
+
+        Ok(result.iter().map(|row| row.into()).collect())
+    }
+
+    async fn insert_many(&self, changes: &[FieldChangeCreateModel]) -> Result<(), DomainError> {
+        let client = self.pool.get().await?;
+
+        let stmt = client.prepare(QUERY_INSERT_FIELD_CHANGE).await?;
+        for change in changes {
+            client.execute(&stmt, &[
+                &change.entity,
+                &change.extid,
+                &change.field,
+                &change.before,
+                &change.after,
+                &change.run_id,
+            ]).await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&Row> for FieldChangeModel {
+    fn from(row: &Row) -> Self {
+        Self {
+            sync_field_changeid: row.get("sync_field_changeid"),
+            field: row.get("field"),
+            before: row.get("before"),
+            after: row.get("after"),
+            run_id: row.get("run_id"),
+            changed_at: row.get("changed_at"),
+        }
+    }
+}
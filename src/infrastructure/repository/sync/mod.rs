@@ -3,4 +3,7 @@ pub mod diff_article;
 pub mod diff_event;
 pub mod diff_group;
 pub mod registered_author;
-pub mod registered_group;
\ No newline at end of file
+pub mod registered_group;pub mod sync_run;
+pub mod sync_lock;
+pub mod dead_letter;
+pub mod field_change;
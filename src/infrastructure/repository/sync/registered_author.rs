@@ -18,6 +18,7 @@ const QUERY_FIND_REGISTERED_AUTHOR: &str = "
         ra.registered_authorid,
         ra.name,
         ra.source,
+        ra.feed_url,
         ra.created_at,
         ra.updated_at,
         count(1) over ()::OID as count
@@ -29,6 +30,7 @@ const QUERY_FIND_REGISTERED_AUTHOR_BY_ID: &str = "
         ra.registered_authorid,
         ra.name,
         ra.source,
+        ra.feed_url,
         ra.created_at,
         ra.updated_at,
         count(1) over ()::OID as count
@@ -38,14 +40,15 @@ const QUERY_FIND_REGISTERED_AUTHOR_BY_ID: &str = "
     registered_authorid = $1;";
 
 const QUERY_INSERT_REGISTERED_AUTHOR: &str = "
-    insert into registered_author(name,source)
+    insert into registered_author(name,source,feed_url)
     values
-        ($1,$2)
+        ($1,$2,$3)
     returning
-        registered_authorid
-        name
-        source
-        created_at
+        registered_authorid,
+        name,
+        source,
+        feed_url,
+        created_at,
         updated_at;";
 
 const QUERY_DELETE_REGISTERED_AUTHOR_BY_ID: &str = "
@@ -78,7 +81,7 @@ impl RegisteredAuthorRepository for PgRegisteredAuthorRepository {
 
         if let Some(name) = name {
             queries.push(format!(
-                "e.name like '%' || ${} || '%'",
+                "ra.name like '%' || ${} || '%'",
                 params.len() + 1
             ));
             params.push(name);
@@ -90,7 +93,7 @@ impl RegisteredAuthorRepository for PgRegisteredAuthorRepository {
         }
 
         let offset = page_size * (page - 1);
-        query = format!("{query} limit {page_size} offset {offset}");
+        query = format!("{query} order by ra.registered_authorid limit {page_size} offset {offset}");
 
         let stmt = client.prepare(&query).await?;
         let result = client.query(&stmt, &params[..]).await?;
@@ -129,6 +132,7 @@ impl RegisteredAuthorRepository for PgRegisteredAuthorRepository {
                 &[
                     &registered_author_create_model.name,
                     &registered_author_create_model.source,
+                    &registered_author_create_model.feed_url,
                 ],
             )
             .await?;
@@ -151,6 +155,7 @@ impl From<&Row> for RegisteredAuthorModel {
             registered_authorid: row.get("registered_authorid"),
             name: row.get("name"),
             source: row.get("source"),
+            feed_url: row.get("feed_url"),
             created_at: row.get("created_at"),
             updated_at: row.get("updated_at"),
         }
@@ -42,10 +42,10 @@ const QUERY_INSERT_REGISTERED_GROUP: &str = "
     values
         ($1,$2)
     returning
-        registered_groupid
-        name
-        source
-        created_at
+        registered_groupid,
+        name,
+        source,
+        created_at,
         updated_at;";
 
 const QUERY_DELETE_REGISTERED_GROUP_BY_ID: &str = "
@@ -90,7 +90,7 @@ impl RegisteredGroupRepository for PgRegisteredGroupRepository {
         }
 
         let offset = page_size * (page - 1);
-        query = format!("{query} limit {page_size} offset {offset}");
+        query = format!("{query} order by rg.registered_groupid limit {page_size} offset {offset}");
 
         let stmt = client.prepare(&query).await?;
         let result = client.query(&stmt, &params[..]).await?;
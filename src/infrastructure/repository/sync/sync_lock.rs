@@ -0,0 +1,81 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_postgres::{Object, Pool};
+
+use crate::domain::{
+    error::DomainError,
+    sync_lock::repository::{SyncLock, SyncLockRepository},
+};
+
+// Session-level advisory locks keyed by ('sync', job), so they never collide
+// with locks taken by other applications on the same database.
+const QUERY_TRY_LOCK_JOB: &str = "
+    select pg_try_advisory_lock(hashtext('sync'), hashtext($1)) as locked;";
+
+const QUERY_UNLOCK_JOB: &str = "
+    select pg_advisory_unlock(hashtext('sync'), hashtext($1));";
+
+pub struct PgSyncLockRepository {
+    pool: Arc<Pool>,
+}
+impl PgSyncLockRepository {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SyncLockRepository for PgSyncLockRepository {
+    async fn try_acquire(&self, job: &str) -> Result<Option<Box<dyn SyncLock>>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_TRY_LOCK_JOB).await?;
+        let locked: bool = client.query_one(&stmt, &[&job]).await?.get("locked");
+
+        if !locked {
+            return Ok(None);
+        }
+
+        Ok(Some(Box::new(PgSyncLock::new(client, job))))
+    }
+}
+
+/// Advisory lock bound to the connection that took it.
+pub struct PgSyncLock {
+    client: Option<Object>,
+    job: String,
+}
+impl PgSyncLock {
+    fn new(client: Object, job: &str) -> Self {
+        Self {
+            client: Some(client),
+            job: job.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl SyncLock for PgSyncLock {
+    async fn release(&mut self) -> Result<(), DomainError> {
+        if let Some(client) = &self.client {
+            let stmt = client.prepare(QUERY_UNLOCK_JOB).await?;
+            client.execute(&stmt, &[&self.job]).await?;
+        }
+
+        // Unlocked, the connection can go back to the pool
+        self.client = None;
+
+        Ok(())
+    }
+}
+
+impl Drop for PgSyncLock {
+    // A lock that was not released (failed unlock, panic) must not go back to
+    // the pool with the session still holding it: closing the connection
+    // releases it instead.
+    fn drop(&mut self) {
+        if let Some(client) = self.client.take() {
+            drop(Object::take(client));
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use deadpool_postgres::Pool;
+
+use tokio_postgres::{types::ToSql, Row};
+
+use crate::domain::{
+    sync_run::{
+        model::{SyncReport, SyncRunCreateModel, SyncRunModel, SyncRunStatus, SyncRunUpdateModel},
+        repository::SyncRunRepository,
+    },
+    error::DomainError,
+};
+
+const QUERY_FIND_SYNC_RUN: &str = "
+    select
+        sr.sync_runid,
+        sr.status,
+        sr.inserted,
+        sr.updated,
+        sr.unchanged,
+        sr.deleted,
+        sr.error,
+        sr.started_at,
+        sr.finished_at,
+        count(1) over ()::OID as count
+    from
+        sync_run sr";
+
+const QUERY_FIND_SYNC_RUN_BY_ID: &str = "
+    select
+        sr.sync_runid,
+        sr.status,
+        sr.inserted,
+        sr.updated,
+        sr.unchanged,
+        sr.deleted,
+        sr.error,
+        sr.started_at,
+        sr.finished_at
+    from
+        sync_run sr
+    where
+        sync_runid = $1;";
+
+const QUERY_FIND_LAST_SUCCEEDED_SYNC_RUN: &str = "
+    select
+        sr.sync_runid,
+        sr.status,
+        sr.inserted,
+        sr.updated,
+        sr.unchanged,
+        sr.deleted,
+        sr.error,
+        sr.started_at,
+        sr.finished_at
+    from
+        sync_run sr
+    where
+        sr.job = $1
+        and sr.status = $2
+    order by
+        sr.started_at desc
+    limit 1;";
+
+const QUERY_INSERT_SYNC_RUN: &str = "
+    insert into sync_run(job,status)
+    values
+        ($1,$2)
+    returning
+        sync_runid,
+        status,
+        inserted,
+        updated,
+        unchanged,
+        deleted,
+        error,
+        started_at,
+        finished_at;";
+
+const QUERY_UPDATE_SYNC_RUN_BY_ID: &str = "
+    update
+        sync_run
+    set
+        status=$2,
+        inserted=$3,
+        updated=$4,
+        unchanged=$5,
+        deleted=$6,
+        error=$7,
+        finished_at=now()
+    where
+        sync_runid = $1
+    returning
+        sync_runid,
+        status,
+        inserted,
+        updated,
+        unchanged,
+        deleted,
+        error,
+        started_at,
+        finished_at;";
+
+pub struct PgSyncRunRepository {
+    pool: Arc<Pool>,
+}
+impl PgSyncRunRepository {
+    pub fn new(pool: Arc<Pool>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SyncRunRepository for PgSyncRunRepository {
+    async fn find(
+        &self,
+        job: &Option<String>,
+        page: &u32,
+        page_size: &u32,
+    ) -> Result<Option<(Vec<SyncRunModel>, u32)>, DomainError> {
+        let client = self.pool.get().await?;
+
+        let mut queries: Vec<String> = vec![];
+        let mut params: Vec<&(dyn ToSql + Sync)> = Vec::new();
+
+        if let Some(job) = job {
+            queries.push(format!("sr.job = ${}", params.len() + 1));
+            params.push(job);
+        }
+
+        let mut query = String::from(QUERY_FIND_SYNC_RUN);
+        if !queries.is_empty() {
+            query = format!("{} where {}", query, queries.join(" and "));
+        }
+
+        let offset = page_size * (page - 1);
+        query = format!("{query} order by sr.started_at desc limit {page_size} offset {offset}");
+
+        let stmt = client.prepare(&query).await?;
+        let result = client.query(&stmt, &params[..]).await?;
+
+        if !result.is_empty() {
+            let count: u32 = result.first().unwrap().get("count");
+
+            let items: Vec<SyncRunModel> = result.iter().map(|row| row.into()).collect();
+
+            return Ok(Some((items, count)));
+        }
+
+        Ok(None)
+    }
+
+    async fn find_by_id(&self, id: &i32) -> Result<Option<SyncRunModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_SYNC_RUN_BY_ID).await?;
+
+        if let Some(result) = client.query_opt(&stmt, &[id]).await? {
+            return Ok(Some((&result).into()));
+        }
+
+        Ok(None)
+    }
+
+    async fn find_last_succeeded(&self, job: &str) -> Result<Option<SyncRunModel>, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_FIND_LAST_SUCCEEDED_SYNC_RUN).await?;
+
+        if let Some(result) = client
+            .query_opt(&stmt, &[&job, &SyncRunStatus::Succeeded.as_str()])
+            .await?
+        {
+            return Ok(Some((&result).into()));
+        }
+
+        Ok(None)
+    }
+
+    async fn insert(
+        &self,
+        sync_run_create_model: &SyncRunCreateModel,
+    ) -> Result<SyncRunModel, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_INSERT_SYNC_RUN).await?;
+        let result = &client
+            .query_one(
+                &stmt,
+                &[
+                    &sync_run_create_model.job,
+                    &SyncRunStatus::Running.as_str(),
+                ],
+            )
+            .await?;
+
+        Ok(result.into())
+    }
+
+    async fn update_by_id(
+        &self,
+        id: &i32,
+        sync_run_update_model: &SyncRunUpdateModel,
+    ) -> Result<SyncRunModel, DomainError> {
+        let client = self.pool.get().await?;
+        let stmt = client.prepare(QUERY_UPDATE_SYNC_RUN_BY_ID).await?;
+        let report = sync_run_update_model.report;
+        let result = &client
+            .query_one(
+                &stmt,
+                &[
+                    id,
+                    &sync_run_update_model.status.as_str(),
+                    &report.map(|report| report.inserted),
+                    &report.map(|report| report.updated),
+                    &report.map(|report| report.unchanged),
+                    &report.map(|report| report.deleted),
+                    &sync_run_update_model.error,
+                ],
+            )
+            .await?;
+
+        Ok(result.into())
+    }
+}
+
+impl From<&Row> for SyncRunModel {
+    fn from(row: &Row) -> Self {
+        let inserted: Option<i32> = row.get("inserted");
+        let updated: Option<i32> = row.get("updated");
+        let unchanged: Option<i32> = row.get("unchanged");
+        // Null for runs recorded before deletions were counted
+        let deleted: Option<i32> = row.get("deleted");
+        let status: &str = row.get("status");
+
+        Self {
+            sync_runid: row.get("sync_runid"),
+            status: status.into(),
+            report: match (inserted, updated, unchanged) {
+                (Some(inserted), Some(updated), Some(unchanged)) => Some(SyncReport {
+                    inserted,
+                    updated,
+                    unchanged,
+                    deleted: deleted.unwrap_or_default(),
+                }),
+                _ => None,
+            },
+            error: row.get("error"),
+            started_at: row.get("started_at"),
+            finished_at: row.get("finished_at"),
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::env;
+use std::error::Error;
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+// Service name of the exported traces
+const SERVICE_NAME: &str = "sync";
+
+#[derive(Debug, PartialEq)]
+enum LogFormat {
+    Text,
+    Json,
+}
+
+struct LogConfig {
+    format: LogFormat,
+    filter: String,
+    otlp_endpoint: Option<String>,
+}
+impl LogConfig {
+    fn from_env() -> Self {
+        Self {
+            format: match env::var("SYNC_LOG_FORMAT")
+                .unwrap_or_else(|_| "text".to_string())
+                .as_str()
+            {
+                "text" => LogFormat::Text,
+                "json" => LogFormat::Json,
+                _ => panic!("SYNC_LOG_FORMAT must be text or json"),
+            },
+            filter: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
+            otlp_endpoint: env::var("SYNC_OTLP_ENDPOINT").ok().filter(|endpoint| !endpoint.is_empty()),
+        }
+    }
+}
+
+/// Keeps the trace exporter alive; spans still buffered are only sent on
+/// [`Telemetry::shutdown`].
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    pub fn shutdown(self) {
+        if let Some(tracer_provider) = self.tracer_provider {
+            if let Err(err) = tracer_provider.shutdown() {
+                eprintln!("Failed to export remaining traces: {}", err);
+            }
+        }
+    }
+}
+
+/// Logs to stderr, so stdout stays free for command output such as dry run
+/// reports. JSON lines carry the fields of the current span and its parents
+/// (`job`, `run_id`, `source`, `author`, `group`, ...) so one author or group
+/// can be filtered in a log aggregator.
+///
+/// With `SYNC_OTLP_ENDPOINT` set, the same spans are also exported as traces
+/// to that OTLP/HTTP collector.
+pub fn init() -> Result<Telemetry, Box<dyn Error>> {
+    let log_config = LogConfig::from_env();
+    let filter = EnvFilter::try_new(&log_config.filter)?;
+
+    let tracer_provider = log_config
+        .otlp_endpoint
+        .as_deref()
+        .map(tracer_provider)
+        .transpose()?;
+    let otel_layer = tracer_provider
+        .as_ref()
+        .map(|tracer_provider| tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME)));
+
+    let registry = tracing_subscriber::registry().with(filter).with(otel_layer);
+    match log_config.format {
+        LogFormat::Text => registry
+            .with(fmt::layer().with_writer(std::io::stderr))
+            .try_init()?,
+        LogFormat::Json => registry
+            .with(
+                fmt::layer()
+                    .json()
+                    .with_current_span(true)
+                    .with_span_list(true)
+                    .with_writer(std::io::stderr),
+            )
+            .try_init()?,
+    }
+
+    Ok(Telemetry { tracer_provider })
+}
+
+// Exports spans in batches to `endpoint`, the base URL of an OTLP/HTTP collector
+fn tracer_provider(endpoint: &str) -> Result<SdkTracerProvider, Box<dyn Error>> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Router};
+    use tokio::net::TcpListener;
+
+    type Exports = Arc<Mutex<Vec<(String, Bytes)>>>;
+
+    // A local collector recording the content type and body of each export
+    async fn collector() -> (String, Exports) {
+        let exports: Exports = Arc::default();
+        let router = Router::new()
+            .route(
+                "/v1/traces",
+                post(|State(exports): State<Exports>, headers: HeaderMap, body: Bytes| async move {
+                    let content_type = headers
+                        .get("content-type")
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default()
+                        .to_string();
+                    exports.lock().unwrap().push((content_type, body));
+                }),
+            )
+            .with_state(exports.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        (endpoint, exports)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn it_should_export_spans_to_collector() {
+        let (endpoint, exports) = collector().await;
+
+        tokio::task::spawn_blocking(move || {
+            let tracer_provider = tracer_provider(&endpoint).unwrap();
+            let subscriber = tracing_subscriber::registry()
+                .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME)));
+
+            tracing::subscriber::with_default(subscriber, || {
+                let _job = tracing::info_span!("job", job = "articles").entered();
+                tracing::info_span!("insert_many", entity = "article").in_scope(|| {});
+            });
+
+            tracer_provider.shutdown().unwrap();
+        })
+        .await
+        .unwrap();
+
+        let exports = exports.lock().unwrap();
+        assert_eq!(exports.len(), 1);
+        assert_eq!(exports[0].0, "application/x-protobuf");
+        assert!(!exports[0].1.is_empty());
+    }
+}
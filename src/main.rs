@@ -1,36 +1,90 @@
-use api::lib::Scheduler;
+use api::{cli::{Cli, Command, ReportFormat}, commands, lib::{self, Scheduler}};
+use clap::Parser;
 use dotenv::dotenv;
 use std::{sync::Arc};
 
-use infrastructure::repository::{postgres::{postgres}, sync};
+use infrastructure::{repository::{postgres::{postgres}, sync::{self, dead_letter::PgDeadLetterRepository, field_change::PgFieldChangeRepository, registered_author::PgRegisteredAuthorRepository, registered_group::PgRegisteredGroupRepository}}, telemetry};
 mod api;
 mod domain;
 mod infrastructure;
 
+// Exit codes, clap already exits with 2 on invalid usage
+const EXIT_COMMAND_FAILED: i32 = 1;
+const EXIT_STARTUP_FAILED: i32 = 3;
+
 #[tokio::main]
 async fn main() {
     dotenv().ok();
-    env_logger::init();
+    let telemetry = match telemetry::init() {
+        Ok(telemetry) => telemetry,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(EXIT_STARTUP_FAILED)
+        }
+    };
 
-    let pg_pool_result = postgres::init();
-    if pg_pool_result.is_err() {
-        log::error!("{}", pg_pool_result.unwrap_err());
-        std::process::exit(1)
-    }
-    let sync_pool_result = sync::postgres::init();
-    if sync_pool_result.is_err() {
-        log::error!("{}", pg_pool_result.unwrap_err());
-        std::process::exit(1)
+    let cli = Cli::parse();
+
+    let sync_pool = match sync::postgres::init() {
+        Ok(pool) => Arc::new(pool),
+        Err(err) => {
+            tracing::error!("{}", err);
+            std::process::exit(EXIT_STARTUP_FAILED)
+        }
+    };
+
+    if let Err(err) = sync::postgres::run_migrations().await {
+        tracing::error!("{}", err);
+        std::process::exit(EXIT_STARTUP_FAILED)
     }
-    let pg_pool = Arc::new(pg_pool_result.unwrap());
 
-    let sync_pool = Arc::new(sync_pool_result.unwrap());
+    let result = match cli.command.unwrap_or(Command::Daemon) {
+        Command::Authors { command } => {
+            commands::authors::execute(Arc::new(PgRegisteredAuthorRepository::new(sync_pool.clone())), &lib::article_sources(), command).await
+        }
+        Command::History { entity, extid } => {
+            commands::history::execute(Arc::new(PgFieldChangeRepository::new(sync_pool.clone())), entity, &extid).await
+        }
+        command => {
+            let pg_pool = match postgres::init() {
+                Ok(pool) => Arc::new(pool),
+                Err(err) => {
+                    tracing::error!("{}", err);
+                    std::process::exit(EXIT_STARTUP_FAILED)
+                }
+            };
+
+            let scheduler = Scheduler::new(pg_pool.clone(),sync_pool.clone());
+
+            match command {
+                Command::Run { job, dry_run: true, format, force } => scheduler
+                    .dry_run(&job.names(), force.to_force())
+                    .await
+                    .and_then(|report| match format {
+                        ReportFormat::Human => Ok(report.to_string()),
+                        ReportFormat::Json => report.to_json().map(|json| format!("{}\n", json)),
+                    })
+                    .map(|report| print!("{}", report)),
+                Command::Run { job, force, .. } => scheduler.run_once(&job.names(), force.to_force()).await,
+                // The group adapters are built on the main database
+                Command::Groups { command } => {
+                    commands::groups::execute(Arc::new(PgRegisteredGroupRepository::new(sync_pool.clone())), &lib::group_sources(pg_pool.clone()), command).await
+                }
+                Command::DeadLetters { command } => {
+                    commands::dead_letters::execute(Arc::new(PgDeadLetterRepository::new(sync_pool.clone())), &scheduler, command).await
+                }
+                _ => scheduler.run().await,
+            }
+        }
+    };
+    if let Err(err) = &result {
+        tracing::error!("{}", err);
+    }
 
-    let scheduler = Scheduler::new(pg_pool.clone(),sync_pool.clone());
+    // Sends the spans of the last runs before exiting
+    telemetry.shutdown();
 
-    let result = scheduler.run().await;
     if result.is_err() {
-        log::error!("{}", result.unwrap_err().to_string());
-        std::process::exit(1)
+        std::process::exit(EXIT_COMMAND_FAILED)
     }
 }
@@ -0,0 +1,25 @@
+CREATE TABLE "sync_run" (
+    "sync_runid" SERIAL NOT NULL,
+    "job" varchar NOT NULL,
+    "status" varchar NOT NULL,
+    "inserted" int4,
+    "updated" int4,
+    "unchanged" int4,
+    "error" varchar,
+    "started_at" timestamptz NOT NULL DEFAULT now(),
+    "finished_at" timestamptz,
+    PRIMARY KEY ("sync_runid")
+);
+
+CREATE INDEX "sync_run_job_started_at_idx" ON "sync_run" ("job", "started_at" DESC);
+
+-- Column Comment
+COMMENT ON COLUMN "sync_run"."sync_runid" IS 'Primary key';
+COMMENT ON COLUMN "sync_run"."job" IS 'Sync job name (articles, groups, events)';
+COMMENT ON COLUMN "sync_run"."status" IS 'running, succeeded or failed';
+COMMENT ON COLUMN "sync_run"."inserted" IS 'Items inserted by the run';
+COMMENT ON COLUMN "sync_run"."updated" IS 'Items updated by the run';
+COMMENT ON COLUMN "sync_run"."unchanged" IS 'Items fetched without changes';
+COMMENT ON COLUMN "sync_run"."error" IS 'Error of a failed run';
+COMMENT ON COLUMN "sync_run"."started_at" IS 'Start timestamp';
+COMMENT ON COLUMN "sync_run"."finished_at" IS 'End timestamp';
@@ -0,0 +1 @@
+COMMENT ON COLUMN "sync_run"."status" IS 'running, succeeded, failed or interrupted';
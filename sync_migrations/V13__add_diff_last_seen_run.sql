@@ -0,0 +1,14 @@
+ALTER TABLE "diff_article" ADD COLUMN "last_seen_run" int4, ADD COLUMN "missed_runs" int4 NOT NULL DEFAULT 0;
+ALTER TABLE "diff_group" ADD COLUMN "last_seen_run" int4, ADD COLUMN "missed_runs" int4 NOT NULL DEFAULT 0;
+ALTER TABLE "diff_event" ADD COLUMN "last_seen_run" int4, ADD COLUMN "missed_runs" int4 NOT NULL DEFAULT 0;
+
+ALTER TABLE "sync_run" ADD COLUMN "deleted" int4;
+
+-- Column Comment
+COMMENT ON COLUMN "diff_article"."last_seen_run" IS 'Last sync_run that fetched the article';
+COMMENT ON COLUMN "diff_article"."missed_runs" IS 'Complete sync runs in a row that did not fetch the article';
+COMMENT ON COLUMN "diff_group"."last_seen_run" IS 'Last sync_run that fetched the group';
+COMMENT ON COLUMN "diff_group"."missed_runs" IS 'Complete sync runs in a row that did not fetch the group';
+COMMENT ON COLUMN "diff_event"."last_seen_run" IS 'Last sync_run that fetched the event';
+COMMENT ON COLUMN "diff_event"."missed_runs" IS 'Complete sync runs in a row that did not fetch the event';
+COMMENT ON COLUMN "sync_run"."deleted" IS 'Items deleted by the run after missing from their source';
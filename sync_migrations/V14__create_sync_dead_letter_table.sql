@@ -0,0 +1,28 @@
+CREATE TABLE "sync_dead_letter" (
+    "sync_dead_letterid" SERIAL NOT NULL,
+    "job" varchar NOT NULL,
+    "source" varchar NOT NULL,
+    "scope" varchar NOT NULL,
+    "key" varchar NOT NULL,
+    "payload" varchar,
+    "error" varchar NOT NULL,
+    "attempts" int4 NOT NULL DEFAULT 1,
+    "created_at" timestamptz NOT NULL DEFAULT now(),
+    "updated_at" timestamptz,
+    PRIMARY KEY ("sync_dead_letterid"),
+    UNIQUE ("job", "source", "key")
+);
+
+CREATE INDEX "sync_dead_letter_job_scope_idx" ON "sync_dead_letter" ("job", "source", "scope");
+
+-- Column Comment
+COMMENT ON COLUMN "sync_dead_letter"."sync_dead_letterid" IS 'Primary key';
+COMMENT ON COLUMN "sync_dead_letter"."job" IS 'Sync job name (articles, groups, events)';
+COMMENT ON COLUMN "sync_dead_letter"."source" IS 'Source of the item (medium, meetup)';
+COMMENT ON COLUMN "sync_dead_letter"."scope" IS 'Author or group the item was fetched for';
+COMMENT ON COLUMN "sync_dead_letter"."key" IS 'Extid of the item, or the scope when the whole author or group failed';
+COMMENT ON COLUMN "sync_dead_letter"."payload" IS 'Excerpt of the upstream payload that failed';
+COMMENT ON COLUMN "sync_dead_letter"."error" IS 'Last error';
+COMMENT ON COLUMN "sync_dead_letter"."attempts" IS 'Runs the item failed in';
+COMMENT ON COLUMN "sync_dead_letter"."created_at" IS 'First failure timestamp';
+COMMENT ON COLUMN "sync_dead_letter"."updated_at" IS 'Last failure timestamp';
@@ -0,0 +1,24 @@
+CREATE TABLE "sync_field_change" (
+    "sync_field_changeid" SERIAL NOT NULL,
+    "entity" varchar NOT NULL,
+    "extid" varchar NOT NULL,
+    "field" varchar NOT NULL,
+    "before" varchar,
+    "after" varchar,
+    "run_id" int4,
+    "changed_at" timestamptz NOT NULL DEFAULT now(),
+    PRIMARY KEY ("sync_field_changeid")
+);
+
+CREATE INDEX "sync_field_change_entity_extid_idx" ON "sync_field_change" ("entity", "extid");
+CREATE INDEX "sync_field_change_run_id_idx" ON "sync_field_change" ("run_id");
+
+-- Column Comment
+COMMENT ON COLUMN "sync_field_change"."sync_field_changeid" IS 'Primary key';
+COMMENT ON COLUMN "sync_field_change"."entity" IS 'Entity of the item (article, group, event)';
+COMMENT ON COLUMN "sync_field_change"."extid" IS 'Extid of the item';
+COMMENT ON COLUMN "sync_field_change"."field" IS 'Changed field';
+COMMENT ON COLUMN "sync_field_change"."before" IS 'Value stored before the sync, null when unset';
+COMMENT ON COLUMN "sync_field_change"."after" IS 'Value written by the sync, null when unset';
+COMMENT ON COLUMN "sync_field_change"."run_id" IS 'sync_run that wrote the change, null outside a recorded run';
+COMMENT ON COLUMN "sync_field_change"."changed_at" IS 'Change timestamp';
@@ -0,0 +1,4 @@
+ALTER TABLE "registered_author" ADD COLUMN "feed_url" varchar;
+
+-- Column Comment
+COMMENT ON COLUMN "registered_author"."feed_url" IS 'RSS or Atom feed of the author, required by the feed source and overriding the feed of the other sources';
@@ -0,0 +1,16 @@
+-- Rows not fetched since this migration have no scope, so no run counts them missing
+ALTER TABLE "diff_article" ADD COLUMN "source" varchar(255), ADD COLUMN "scope" varchar(255);
+ALTER TABLE "diff_group" ADD COLUMN "source" varchar(255), ADD COLUMN "scope" varchar(255);
+ALTER TABLE "diff_event" ADD COLUMN "source" varchar(255), ADD COLUMN "scope" varchar(255);
+
+CREATE INDEX "diff_article_scope_idx" ON "diff_article" ("source", "scope");
+CREATE INDEX "diff_group_scope_idx" ON "diff_group" ("source", "scope");
+CREATE INDEX "diff_event_scope_idx" ON "diff_event" ("source", "scope");
+
+-- Column Comment
+COMMENT ON COLUMN "diff_article"."source" IS 'Source the article was last fetched from';
+COMMENT ON COLUMN "diff_article"."scope" IS 'Author the article was last fetched for';
+COMMENT ON COLUMN "diff_group"."source" IS 'Source the group was last fetched from';
+COMMENT ON COLUMN "diff_group"."scope" IS 'Slug of the group';
+COMMENT ON COLUMN "diff_event"."source" IS 'Source the event was last fetched from';
+COMMENT ON COLUMN "diff_event"."scope" IS 'Group the event was last fetched for';
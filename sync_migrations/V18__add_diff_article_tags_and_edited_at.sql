@@ -0,0 +1,6 @@
+-- Kept here rather than in the main database, which this service does not migrate
+ALTER TABLE "diff_article" ADD COLUMN "tags" varchar[] NOT NULL DEFAULT '{}', ADD COLUMN "edited_at" timestamptz;
+
+-- Column Comment
+COMMENT ON COLUMN "diff_article"."tags" IS 'Tags of the article at its source';
+COMMENT ON COLUMN "diff_article"."edited_at" IS 'Last edit date at the source';
@@ -0,0 +1,4 @@
+-- Groups are scoped by their registered name and events by their group slug,
+-- the keys of their dead letters. Rows keep their former scope until fetched again.
+COMMENT ON COLUMN "diff_group"."scope" IS 'Name the group is registered under';
+COMMENT ON COLUMN "diff_event"."scope" IS 'Slug of the group the event was last fetched for';
@@ -0,0 +1,10 @@
+-- Feeds only list the latest articles and Meetup only the upcoming events, so
+-- only the rows dated within what their source lists can be missing from it.
+-- Rows are dated when next written, the checksum versions of articles and
+-- events being bumped for that, and undated rows are never counted missing.
+ALTER TABLE "diff_article" ADD COLUMN "publish_at" timestamptz;
+ALTER TABLE "diff_event" ADD COLUMN "time" timestamptz;
+
+-- Column Comment
+COMMENT ON COLUMN "diff_article"."publish_at" IS 'Publish date of the article';
+COMMENT ON COLUMN "diff_event"."time" IS 'Start time of the event';